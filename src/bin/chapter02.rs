@@ -30,14 +30,12 @@ fn main() -> Result<(), std::io::Error> {
     };
 
     let mut canvas = Canvas::new(Coord2D::new(900, 550));
-    let y_max = canvas.get_dim().y - 1;
 
     while projectile.position.get_y() > Float(0.0) {
         projectile = tick(environment, projectile);
-        canvas[Coord2D::new(
-            projectile.position.get_x().0 as usize,
-            y_max - projectile.position.get_y().0 as usize,
-        )] = Color::new_from_underlying(1.0, 0.0, 0.0);
+        if let Some(coord) = canvas.world_to_screen(projectile.position) {
+            canvas[coord] = Color::new_from_underlying(1.0, 0.0, 0.0);
+        }
     }
 
     let file = std::fs::File::create("chapter02.ppm")?;
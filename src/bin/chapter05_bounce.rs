@@ -0,0 +1,99 @@
+use rtlib::prelude::*;
+
+/// Point bouncing around inside the canvas
+#[derive(Copy, Clone, Debug)]
+struct Bouncer {
+    position: Tuple,
+    direction: Tuple,
+}
+
+/// Returns the inward normal of the wall the point has crossed, if any
+fn wall_normal(position: Tuple, dim: Coord2D) -> Option<Tuple> {
+    let max_x = dim.x as UnderlyingFloat - 1.0;
+    let max_y = dim.y as UnderlyingFloat - 1.0;
+    let x = position.get_underlying_x();
+    let y = position.get_underlying_y();
+    if x < 0.0 {
+        Some(Tuple::vector_from_underlying(1.0, 0.0, 0.0))
+    } else if x > max_x {
+        Some(Tuple::vector_from_underlying(-1.0, 0.0, 0.0))
+    } else if y < 0.0 {
+        Some(Tuple::vector_from_underlying(0.0, 1.0, 0.0))
+    } else if y > max_y {
+        Some(Tuple::vector_from_underlying(0.0, -1.0, 0.0))
+    } else {
+        None
+    }
+}
+
+fn step(bouncer: Bouncer, speed: Float, dim: Coord2D) -> Bouncer {
+    let mut next = Bouncer {
+        position: bouncer.position + bouncer.direction * speed,
+        direction: bouncer.direction,
+    };
+    while let Some(normal) = wall_normal(next.position, dim) {
+        next.direction = next.direction.reflect(normal);
+        next.position = bouncer.position + next.direction * speed;
+    }
+    next
+}
+
+fn main() -> Result<(), std::io::Error> {
+    let dim = Coord2D::new(300, 200);
+    let mut canvas = Canvas::new(dim);
+
+    let mut bouncer = Bouncer {
+        position: Tuple::point_from_underlying(20.0, 30.0, 0.0),
+        direction: Tuple::vector_from_underlying(1.0, 0.7, 0.0).normalize(),
+    };
+
+    let steps = 2000;
+    let tail = Color::new_from_underlying(0.05, 0.05, 0.2);
+    let head = Color::new_from_underlying(1.0, 0.8, 0.2);
+
+    for i in 0..steps {
+        let t = Float(i as UnderlyingFloat / (steps - 1) as UnderlyingFloat);
+        if let Some(coord) = canvas.world_to_screen(bouncer.position) {
+            canvas.plot(coord, tail.lerp(head, t));
+        }
+        bouncer = step(bouncer, Float(1.0), dim);
+    }
+
+    let file = std::fs::File::create("chapter05_bounce.ppm")?;
+    let mut buffered_file = std::io::BufWriter::new(file);
+
+    canvas.write_ppm(&mut buffered_file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reflect_off_walls() {
+        let v = Tuple::vector_from_underlying(1.0, -1.0, 0.0);
+        assert_eq!(
+            v.reflect(Tuple::vector_from_underlying(0.0, 1.0, 0.0)),
+            Tuple::vector_from_underlying(1.0, 1.0, 0.0)
+        );
+        assert_eq!(
+            v.reflect(Tuple::vector_from_underlying(-1.0, 0.0, 0.0)),
+            Tuple::vector_from_underlying(-1.0, -1.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn step_bounces_inside_box() {
+        let dim = Coord2D::new(10, 10);
+        let bouncer = Bouncer {
+            position: Tuple::point_from_underlying(8.5, 5.0, 0.0),
+            direction: Tuple::vector_from_underlying(1.0, 0.0, 0.0),
+        };
+        let next = step(bouncer, Float(1.0), dim);
+        assert_eq!(next.position, Tuple::point_from_underlying(7.5, 5.0, 0.0));
+        assert_eq!(
+            next.direction,
+            Tuple::vector_from_underlying(-1.0, 0.0, 0.0)
+        );
+    }
+}
@@ -0,0 +1,231 @@
+use crate::canvas::DimMismatch;
+use crate::prelude::*;
+use std::collections::HashMap;
+
+#[derive(Debug)]
+pub enum GifError {
+    NoFrames,
+    TooLarge(Coord2D),
+    DimMismatch(DimMismatch),
+    Encoding(gif::EncodingError),
+}
+
+impl std::fmt::Display for GifError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GifError::NoFrames => write!(f, "no frames to encode"),
+            GifError::TooLarge(dim) => write!(f, "{} exceeds the GIF size limit", dim),
+            GifError::DimMismatch(err) => write!(f, "{}", err),
+            GifError::Encoding(err) => write!(f, "gif encoding failed: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for GifError {}
+
+impl From<DimMismatch> for GifError {
+    fn from(err: DimMismatch) -> Self {
+        GifError::DimMismatch(err)
+    }
+}
+
+impl From<gif::EncodingError> for GifError {
+    fn from(err: gif::EncodingError) -> Self {
+        GifError::Encoding(err)
+    }
+}
+
+type Rgb = [u8; 3];
+
+fn median_cut(pixels: Vec<Rgb>, max_colors: usize) -> Vec<Rgb> {
+    let channel_range = |bucket: &[Rgb], channel: usize| {
+        let (min, max) = bucket.iter().fold((u8::MAX, u8::MIN), |(min, max), p| {
+            (min.min(p[channel]), max.max(p[channel]))
+        });
+        max.saturating_sub(min)
+    };
+    let widest_channel = |bucket: &[Rgb]| {
+        (0..3)
+            .map(|channel| (channel_range(bucket, channel), channel))
+            .max()
+            .unwrap()
+    };
+
+    let mut buckets = vec![pixels];
+    while buckets.len() < max_colors {
+        let candidate = buckets
+            .iter()
+            .enumerate()
+            .map(|(index, bucket)| (widest_channel(bucket), index))
+            .filter(|((range, _), _)| *range > 0)
+            .max();
+        let Some(((_, channel), index)) = candidate else {
+            break;
+        };
+        let mut bucket = buckets.swap_remove(index);
+        bucket.sort_unstable_by_key(|p| p[channel]);
+        let upper = bucket.split_off(bucket.len() / 2);
+        buckets.push(bucket);
+        buckets.push(upper);
+    }
+
+    buckets
+        .iter()
+        .filter(|bucket| !bucket.is_empty())
+        .map(|bucket| {
+            let mut sum = [0usize; 3];
+            bucket
+                .iter()
+                .for_each(|p| (0..3).for_each(|channel| sum[channel] += p[channel] as usize));
+            sum.map(|s| ((s + bucket.len() / 2) / bucket.len()) as u8)
+        })
+        .collect()
+}
+
+fn nearest_index(palette: &[Rgb], pixel: Rgb) -> u8 {
+    let distance = |entry: &Rgb| -> i32 {
+        (0..3)
+            .map(|channel| (entry[channel] as i32 - pixel[channel] as i32).pow(2))
+            .sum()
+    };
+    palette
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, entry)| distance(entry))
+        .map(|(index, _)| index as u8)
+        .unwrap_or(0)
+}
+
+impl Canvas {
+    fn rgb_pixels(&self) -> impl Iterator<Item = Rgb> + '_ {
+        self.data.iter().map(|color| {
+            [
+                Self::u8_representation(color.get_r()),
+                Self::u8_representation(color.get_g()),
+                Self::u8_representation(color.get_b()),
+            ]
+        })
+    }
+
+    /// Writes the canvas as a single frame GIF
+    pub fn write_gif(&self, w: &mut impl std::io::Write) -> Result<(), GifError> {
+        Self::write_gif_animation(std::slice::from_ref(self), 0, w)
+    }
+
+    /// Writes the frames as a looping GIF animation with delay_cs hundredths of a second
+    /// between frames
+    ///
+    /// All frames share a 256 color palette generated by median cut.
+    pub fn write_gif_animation(
+        frames: &[Canvas],
+        delay_cs: u16,
+        w: &mut impl std::io::Write,
+    ) -> Result<(), GifError> {
+        let first = frames.first().ok_or(GifError::NoFrames)?;
+        for frame in frames {
+            first.check_dim(frame)?;
+        }
+        let dim = first.dim;
+        let (width, height) = match (u16::try_from(dim.x), u16::try_from(dim.y)) {
+            (Ok(width), Ok(height)) => (width, height),
+            _ => return Err(GifError::TooLarge(dim)),
+        };
+
+        let mut palette = median_cut(frames.iter().flat_map(|f| f.rgb_pixels()).collect(), 256);
+        if palette.is_empty() {
+            palette.push([0, 0, 0]);
+        }
+        let mut encoder = gif::Encoder::new(w, width, height, palette.as_flattened())?;
+        if frames.len() > 1 {
+            encoder.set_repeat(gif::Repeat::Infinite)?;
+        }
+
+        let mut cache: HashMap<Rgb, u8> = HashMap::new();
+        for frame in frames {
+            let indices: Vec<u8> = frame
+                .rgb_pixels()
+                .map(|pixel| {
+                    *cache
+                        .entry(pixel)
+                        .or_insert_with(|| nearest_index(&palette, pixel))
+                })
+                .collect();
+            let mut gif_frame = gif::Frame::from_indexed_pixels(width, height, indices, None);
+            gif_frame.delay = delay_cs;
+            encoder.write_frame(&gif_frame)?;
+        }
+        encoder.into_inner()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn median_cut_separates_clusters() {
+        let mut pixels = vec![[250, 0, 0]; 10];
+        pixels.extend(vec![[0, 0, 250]; 10]);
+        pixels.push([254, 2, 0]);
+        let mut palette = median_cut(pixels, 2);
+        palette.sort();
+        assert_eq!(palette, [[0, 0, 250], [250, 0, 0]]);
+    }
+
+    #[test]
+    fn median_cut_stops_on_uniform_input() {
+        assert_eq!(median_cut(vec![[7, 8, 9]; 5], 256), [[7, 8, 9]]);
+    }
+
+    #[test]
+    fn write_gif_animation() {
+        let dim = Coord2D::new(4, 3);
+        let mut first = Canvas::new(dim);
+        first[Coord2D::new(0, 0)] = Color::new_from_underlying(1.0, 0.0, 0.0);
+        let mut second = Canvas::new(dim);
+        second[Coord2D::new(3, 2)] = Color::new_from_underlying(0.0, 1.0, 0.0);
+
+        let mut data: Vec<u8> = Vec::new();
+        let res = Canvas::write_gif_animation(&[first, second], 10, &mut data);
+        assert!(res.is_ok());
+        assert_eq!(&data[..6], b"GIF89a");
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = options.read_info(data.as_slice()).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (4, 3));
+        let mut frames = Vec::new();
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            frames.push((frame.delay, frame.buffer.to_vec()));
+        }
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].0, 10);
+        assert_eq!(frames[0].1[..4], [255, 0, 0, 255]);
+        assert_eq!(frames[1].1[44..], [0, 255, 0, 255]);
+    }
+
+    #[test]
+    fn write_gif_validates_frames() {
+        let mut data: Vec<u8> = Vec::new();
+        assert!(matches!(
+            Canvas::write_gif_animation(&[], 10, &mut data),
+            Err(GifError::NoFrames)
+        ));
+        let frames = [
+            Canvas::new(Coord2D::new(2, 2)),
+            Canvas::new(Coord2D::new(3, 2)),
+        ];
+        assert!(matches!(
+            Canvas::write_gif_animation(&frames, 10, &mut data),
+            Err(GifError::DimMismatch(_))
+        ));
+    }
+
+    #[test]
+    fn write_gif_single_frame() {
+        let mut data: Vec<u8> = Vec::new();
+        assert!(Canvas::new(Coord2D::new(2, 2)).write_gif(&mut data).is_ok());
+        assert_eq!(&data[..6], b"GIF89a");
+    }
+}
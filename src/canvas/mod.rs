@@ -1,12 +1,119 @@
 pub mod coord_2d;
+#[cfg(feature = "gif")]
+pub mod gif_export;
+#[cfg(feature = "png")]
+pub mod png_export;
+pub mod supersampled;
+pub mod viewport;
 
 use crate::prelude::*;
 use std::ops::{Index, IndexMut};
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Axis {
+    Row,
+    Column,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DimMismatch {
+    pub expected: Coord2D,
+    pub actual: Coord2D,
+}
+
+impl std::fmt::Display for DimMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "canvas dimension mismatch: expected {}, got {}",
+            self.expected, self.actual
+        )
+    }
+}
+
+impl std::error::Error for DimMismatch {}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RaggedRows {
+    pub row: usize,
+    pub expected: usize,
+    pub actual: usize,
+}
+
+impl std::fmt::Display for RaggedRows {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "row {} has length {}, expected {}",
+            self.row, self.actual, self.expected
+        )
+    }
+}
+
+impl std::error::Error for RaggedRows {}
+
+#[derive(Debug)]
+pub enum PpmError {
+    Io(std::io::Error),
+    BadMagic(String),
+    BadHeader(String),
+    SampleCount { expected: usize, actual: usize },
+    InvalidSample(String),
+    SampleOverflow { sample: u64, maxval: u32 },
+}
+
+impl std::fmt::Display for PpmError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PpmError::Io(e) => write!(f, "i/o error reading ppm: {}", e),
+            PpmError::BadMagic(magic) => write!(f, "unexpected ppm magic {:?}", magic),
+            PpmError::BadHeader(msg) => write!(f, "bad ppm header: {}", msg),
+            PpmError::SampleCount { expected, actual } => {
+                write!(f, "expected {} ppm samples, got {}", expected, actual)
+            }
+            PpmError::InvalidSample(token) => write!(f, "invalid ppm sample {:?}", token),
+            PpmError::SampleOverflow { sample, maxval } => {
+                write!(f, "ppm sample {} exceeds maxval {}", sample, maxval)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PpmError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PpmError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for PpmError {
+    fn from(e: std::io::Error) -> Self {
+        PpmError::Io(e)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RegionStats {
+    pub min_luminance: Float,
+    pub max_luminance: Float,
+    pub mean_luminance: Float,
+    pub mean_color: Color,
+}
+
+#[derive(Clone, Debug)]
 pub struct Canvas {
     data: Vec<Color>,
     dim: Coord2D,
+    weights: Vec<Float>,
+}
+
+/// Compares pixels only, splat weights are bookkeeping for resolve_splats
+impl PartialEq for Canvas {
+    fn eq(&self, other: &Self) -> bool {
+        self.dim == other.dim && self.data == other.data
+    }
 }
 
 impl Canvas {
@@ -24,7 +131,181 @@ impl Canvas {
         Self {
             data: vec![Color::new_from_underlying(0.0, 0.0, 0.0); dim.x * dim.y],
             dim,
+            weights: Vec::new(),
+        }
+    }
+
+    /// Creates canvas by evaluating f for every pixel, reporting progress per row
+    ///
+    /// progress is called after each completed row with the number of done and total rows.
+    ///
+    /// ```
+    /// use rtlib::canvas::Canvas;
+    /// use rtlib::canvas::coord_2d::Coord2D;
+    /// use rtlib::color::Color;
+    /// let mut reports = Vec::new();
+    /// let canvas = Canvas::from_fn_with_progress(
+    ///     Coord2D::new(2, 2),
+    ///     |coord| Color::new_from_underlying(coord.x as f64, coord.y as f64, 0.0),
+    ///     |done, total| reports.push((done, total)),
+    /// );
+    /// assert_eq!(canvas[Coord2D::new(1, 0)], Color::new_from_underlying(1.0, 0.0, 0.0));
+    /// assert_eq!(reports, [(1, 2), (2, 2)]);
+    /// ```
+    pub fn from_fn_with_progress<F, P>(dim: Coord2D, mut f: F, mut progress: P) -> Self
+    where
+        F: FnMut(Coord2D) -> Color,
+        P: FnMut(usize, usize),
+    {
+        let mut res = Self::new(dim);
+        for y in 0..dim.y {
+            for x in 0..dim.x {
+                let coord = Coord2D::new(x, y);
+                res[coord] = f(coord);
+            }
+            progress(y + 1, dim.y);
+        }
+        res
+    }
+
+    /// Creates canvas from rows of pixels, taking the width from the first row
+    ///
+    /// ```
+    /// use rtlib::canvas::Canvas;
+    /// use rtlib::canvas::coord_2d::Coord2D;
+    /// use rtlib::color::Color;
+    /// let red = Color::new_from_underlying(1.0, 0.0, 0.0);
+    /// let canvas = Canvas::from_rows([vec![red; 2], vec![red; 2]]).unwrap();
+    /// assert_eq!(canvas.get_dim(), Coord2D::new(2, 2));
+    /// assert!(Canvas::from_rows([vec![red; 2], vec![red; 1]]).is_err());
+    /// ```
+    pub fn from_rows<I>(rows: I) -> Result<Canvas, RaggedRows>
+    where
+        I: IntoIterator<Item = Vec<Color>>,
+    {
+        let mut data = Vec::new();
+        let mut width = 0;
+        let mut height = 0;
+        for row in rows {
+            if height == 0 {
+                width = row.len();
+            } else if row.len() != width {
+                return Err(RaggedRows {
+                    row: height,
+                    expected: width,
+                    actual: row.len(),
+                });
+            }
+            data.extend(row);
+            height += 1;
         }
+        Ok(Self {
+            data,
+            dim: Coord2D::new(width, height),
+            weights: Vec::new(),
+        })
+    }
+
+    /// Creates a factor times larger canvas that resolves to dim for antialiasing
+    pub fn with_supersampling(dim: Coord2D, factor: usize) -> supersampled::Supersampled {
+        supersampled::Supersampled::new(dim, factor)
+    }
+
+    /// Creates a black canvas matching the pixel dimension of viewport
+    pub fn for_viewport(viewport: &viewport::Viewport) -> Self {
+        Self::new(viewport.dim)
+    }
+
+    /// Creates a color wheel inscribed in the canvas
+    ///
+    /// Hue follows the angle around the center (0° pointing right, counterclockwise),
+    /// saturation grows with the radius and value is 1. Pixels outside the circle are black.
+    pub fn color_wheel(dim: Coord2D) -> Self {
+        let mut res = Self::new(dim);
+        let center_x = dim.x as UnderlyingFloat / 2.0;
+        let center_y = dim.y as UnderlyingFloat / 2.0;
+        let radius = Float(center_x.min(center_y));
+        res.iter_mut().for_each(|(coord, color)| {
+            let dx = Float(coord.x as UnderlyingFloat + 0.5 - center_x);
+            let dy = Float(center_y - coord.y as UnderlyingFloat - 0.5);
+            let r = dy.hypot(dx);
+            if r.0 <= radius.0 {
+                let hue = Float(dy.atan2(dx).0.to_degrees());
+                *color = Color::from_hsv(hue, r / radius, Float(1.0));
+            }
+        });
+        res
+    }
+
+    fn lattice_value(x: i64, y: i64, seed: u64) -> UnderlyingFloat {
+        let mut h = seed
+            ^ (x as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+            ^ (y as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F);
+        h ^= h >> 30;
+        h = h.wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        h ^= h >> 27;
+        h = h.wrapping_mul(0x94D0_49BB_1331_11EB);
+        h ^= h >> 31;
+        (h >> 11) as UnderlyingFloat / (1u64 << 53) as UnderlyingFloat
+    }
+
+    fn value_noise_at(x: UnderlyingFloat, y: UnderlyingFloat, seed: u64) -> UnderlyingFloat {
+        let smoothstep = |t: UnderlyingFloat| Float(t).smoothstep(Float(0.0), Float(1.0)).0;
+        let (x0, y0) = (x.floor(), y.floor());
+        let (tx, ty) = (smoothstep(x - x0), smoothstep(y - y0));
+        let (ix, iy) = (x0 as i64, y0 as i64);
+        let lerp = |a: UnderlyingFloat, b: UnderlyingFloat, t| a + (b - a) * t;
+        let top = lerp(
+            Self::lattice_value(ix, iy, seed),
+            Self::lattice_value(ix + 1, iy, seed),
+            tx,
+        );
+        let bottom = lerp(
+            Self::lattice_value(ix, iy + 1, seed),
+            Self::lattice_value(ix + 1, iy + 1, seed),
+            tx,
+        );
+        lerp(top, bottom, ty)
+    }
+
+    /// Creates a grayscale value noise field with lattice cells of scale pixels
+    ///
+    /// Panics if scale is not positive.
+    pub fn value_noise(dim: Coord2D, scale: Float, seed: u64) -> Self {
+        Self::fbm_noise(dim, scale, 1, seed)
+    }
+
+    /// Creates grayscale fractal noise by summing octaves of value noise
+    ///
+    /// Each octave doubles the frequency and halves the amplitude of the previous one.
+    /// The result is normalized to [0, 1]. Panics if scale is not positive.
+    pub fn fbm_noise(dim: Coord2D, scale: Float, octaves: usize, seed: u64) -> Self {
+        assert!(scale.0 > 0.0, "noise scale must be positive");
+        let mut res = Self::new(dim);
+        res.iter_mut().for_each(|(coord, color)| {
+            let mut sum = 0.0;
+            let mut total_amplitude = 0.0;
+            let mut amplitude = 1.0;
+            let mut frequency = 1.0 / scale.0;
+            for octave in 0..octaves {
+                sum += amplitude
+                    * Self::value_noise_at(
+                        coord.x as UnderlyingFloat * frequency,
+                        coord.y as UnderlyingFloat * frequency,
+                        seed.wrapping_add(octave as u64),
+                    );
+                total_amplitude += amplitude;
+                amplitude *= 0.5;
+                frequency *= 2.0;
+            }
+            let value = if total_amplitude > 0.0 {
+                sum / total_amplitude
+            } else {
+                0.0
+            };
+            *color = Color::new_from_underlying(value, value, value);
+        });
+        res
     }
 
     /// Gets the dimension of canvas
@@ -96,6 +377,612 @@ impl Canvas {
             .map(move |(index, data)| (Coord2D::new_from_index(index, &dim), data))
     }
 
+    /// Maps a world point (y up) to a canvas coordinate (y down)
+    ///
+    /// Returns None if the point lies outside the canvas.
+    ///
+    /// ```
+    /// use rtlib::canvas::Canvas;
+    /// use rtlib::canvas::coord_2d::Coord2D;
+    /// use rtlib::tuple::Tuple;
+    /// let canvas = Canvas::new(Coord2D::new(10, 5));
+    /// assert_eq!(
+    ///   canvas.world_to_screen(Tuple::point_from_underlying(2.5, 1.2, 0.0)),
+    ///   Some(Coord2D::new(2, 3))
+    /// );
+    /// ```
+    pub fn world_to_screen(&self, p: Tuple) -> Option<Coord2D> {
+        let flipped = p.flip_y();
+        let x = flipped.get_underlying_x().floor();
+        let y = (self.dim.y as UnderlyingFloat - 1.0) + flipped.get_underlying_y().ceil();
+        if x < 0.0
+            || y < 0.0
+            || x >= self.dim.x as UnderlyingFloat
+            || y >= self.dim.y as UnderlyingFloat
+        {
+            return None;
+        }
+        Some(Coord2D::new(x as usize, y as usize))
+    }
+
+    /// Sets a pixel, silently ignoring coordinates outside the canvas
+    ///
+    /// ```
+    /// use rtlib::canvas::Canvas;
+    /// use rtlib::canvas::coord_2d::Coord2D;
+    /// use rtlib::color::Color;
+    /// let red = Color::new_from_underlying(1.0, 0.0, 0.0);
+    /// let mut canvas = Canvas::new(Coord2D::new(2, 2));
+    /// canvas.plot(Coord2D::new(1, 1), red);
+    /// canvas.plot(Coord2D::new(5, 1), red);
+    /// assert_eq!(canvas[Coord2D::new(1, 1)], red);
+    /// ```
+    pub fn plot(&mut self, c: Coord2D, color: Color) {
+        if c.x < self.dim.x && c.y < self.dim.y {
+            self[c] = color;
+        }
+    }
+
+    /// Sets every pixel to color, discarding any accumulated splat weights
+    pub fn fill(&mut self, color: Color) {
+        self.data.fill(color);
+        self.weights.clear();
+    }
+
+    /// Sets every pixel to black
+    pub fn clear(&mut self) {
+        self.fill(Color::new_from_underlying(0.0, 0.0, 0.0));
+    }
+
+    /// Fills the 4-connected region around start whose colors lie within tolerance of the
+    /// color at start
+    pub fn flood_fill_tolerance(&mut self, start: Coord2D, fill: Color, tolerance: Float) {
+        if start.x >= self.dim.x || start.y >= self.dim.y {
+            return;
+        }
+        let seed = self[start];
+        let mut visited = vec![false; self.size()];
+        let mut stack = vec![start];
+        visited[start.to_index(&self.dim)] = true;
+        while let Some(coord) = stack.pop() {
+            self[coord] = fill;
+            let neighbors = [
+                (coord.x.checked_sub(1), Some(coord.y)),
+                (Some(coord.x + 1), Some(coord.y)),
+                (Some(coord.x), coord.y.checked_sub(1)),
+                (Some(coord.x), Some(coord.y + 1)),
+            ];
+            for neighbor in neighbors {
+                if let (Some(x), Some(y)) = neighbor {
+                    let neighbor = Coord2D::new(x, y);
+                    if x >= self.dim.x || y >= self.dim.y {
+                        continue;
+                    }
+                    let index = neighbor.to_index(&self.dim);
+                    if !visited[index] && self.data[index].distance(seed).0 <= tolerance.0 {
+                        visited[index] = true;
+                        stack.push(neighbor);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Yields the coordinates from a to b inclusive along a Bresenham traversal
+    fn line_coords(a: Coord2D, b: Coord2D) -> impl Iterator<Item = Coord2D> {
+        let (mut x, mut y) = (a.x as i64, a.y as i64);
+        let (x1, y1) = (b.x as i64, b.y as i64);
+        let dx = (x1 - x).abs();
+        let dy = -(y1 - y).abs();
+        let sx = if x < x1 { 1 } else { -1 };
+        let sy = if y < y1 { 1 } else { -1 };
+        let mut err = dx + dy;
+        let mut done = false;
+        std::iter::from_fn(move || {
+            if done {
+                return None;
+            }
+            let res = Coord2D::new(x as usize, y as usize);
+            if x == x1 && y == y1 {
+                done = true;
+            } else {
+                let e2 = 2 * err;
+                if e2 >= dy {
+                    err += dy;
+                    x += sx;
+                }
+                if e2 <= dx {
+                    err += dx;
+                    y += sy;
+                }
+            }
+            Some(res)
+        })
+    }
+
+    /// Draws a line from a to b, both ends included, using Bresenham's algorithm
+    ///
+    /// Parts outside the canvas are clipped.
+    ///
+    /// ```
+    /// use rtlib::canvas::Canvas;
+    /// use rtlib::canvas::coord_2d::Coord2D;
+    /// use rtlib::color::Color;
+    /// let mut canvas = Canvas::new(Coord2D::new(3, 3));
+    /// let white = Color::new_from_underlying(1.0, 1.0, 1.0);
+    /// canvas.draw_line(Coord2D::new(2, 0), Coord2D::new(0, 2), white);
+    /// assert_eq!(canvas[Coord2D::new(1, 1)], white);
+    /// ```
+    pub fn draw_line(&mut self, a: Coord2D, b: Coord2D, color: Color) {
+        Self::line_coords(a, b).for_each(|coord| self.plot(coord, color));
+    }
+
+    /// Draws a dashed line from a to b, alternating on set and off skipped pixels
+    ///
+    /// Parts outside the canvas are clipped.
+    pub fn draw_line_dashed(
+        &mut self,
+        a: Coord2D,
+        b: Coord2D,
+        color: Color,
+        on: usize,
+        off: usize,
+    ) {
+        let period = on + off;
+        if on == 0 {
+            return;
+        }
+        Self::line_coords(a, b)
+            .enumerate()
+            .filter(|(index, _)| index % period < on)
+            .for_each(|(_, coord)| self.plot(coord, color));
+    }
+
+    /// Fills a triangle, interpolating the vertex colors with barycentric weights
+    ///
+    /// Pixels are sampled at their centers. Parts outside the canvas are clipped and
+    /// degenerate triangles draw nothing.
+    pub fn fill_triangle_gouraud(&mut self, verts: [(Coord2D, Color); 3]) {
+        let center = |c: Coord2D| (c.x as UnderlyingFloat + 0.5, c.y as UnderlyingFloat + 0.5);
+        let edge = |a: (UnderlyingFloat, UnderlyingFloat),
+                    b: (UnderlyingFloat, UnderlyingFloat),
+                    p: (UnderlyingFloat, UnderlyingFloat)| {
+            (b.0 - a.0) * (p.1 - a.1) - (b.1 - a.1) * (p.0 - a.0)
+        };
+        let [(c0, col0), (c1, col1), (c2, col2)] = verts;
+        let (p0, p1, p2) = (center(c0), center(c1), center(c2));
+        let area = edge(p0, p1, p2);
+        if area.abs() < EPSILON {
+            return;
+        }
+        let x_end = (c0.x.max(c1.x).max(c2.x) + 1).min(self.dim.x);
+        let y_end = (c0.y.max(c1.y).max(c2.y) + 1).min(self.dim.y);
+        for y in c0.y.min(c1.y).min(c2.y)..y_end {
+            for x in c0.x.min(c1.x).min(c2.x)..x_end {
+                let coord = Coord2D::new(x, y);
+                let p = center(coord);
+                let w0 = edge(p1, p2, p) / area;
+                let w1 = edge(p2, p0, p) / area;
+                let w2 = edge(p0, p1, p) / area;
+                if w0 >= 0.0 && w1 >= 0.0 && w2 >= 0.0 {
+                    self[coord] = col0 * w0 + col1 * w1 + col2 * w2;
+                }
+            }
+        }
+    }
+
+    /// Draws a red +x and a green +y axis of the given length starting at origin
+    ///
+    /// The y axis grows downward on the canvas. Parts outside the canvas are clipped.
+    pub fn draw_axes(&mut self, origin: Coord2D, length: usize) {
+        let red = Color::new_from_underlying(1.0, 0.0, 0.0);
+        let green = Color::new_from_underlying(0.0, 1.0, 0.0);
+        if origin.x >= self.dim.x || origin.y >= self.dim.y {
+            return;
+        }
+        let end_x = origin.x.saturating_add(length).min(self.dim.x - 1);
+        let end_y = origin.y.saturating_add(length).min(self.dim.y - 1);
+        self.draw_line(origin, Coord2D::new(end_x, origin.y), red);
+        self.draw_line(origin, Coord2D::new(origin.x, end_y), green);
+    }
+
+    /// Samples the canvas at a fractional coordinate, interpolating the four surrounding pixels
+    ///
+    /// Integer coordinates hit pixels exactly. Returns None outside the span of pixel
+    /// coordinates.
+    ///
+    /// ```
+    /// use rtlib::canvas::Canvas;
+    /// use rtlib::canvas::coord_2d::Coord2D;
+    /// use rtlib::color::Color;
+    /// use rtlib::float::Float;
+    /// let mut canvas = Canvas::new(Coord2D::new(2, 1));
+    /// canvas[1] = Color::new_from_underlying(1.0, 0.0, 0.0);
+    /// assert_eq!(
+    ///     canvas.sample_bilinear(Float(0.25), Float(0.0)),
+    ///     Some(Color::new_from_underlying(0.25, 0.0, 0.0))
+    /// );
+    /// assert_eq!(canvas.sample_bilinear(Float(1.5), Float(0.0)), None);
+    /// ```
+    pub fn sample_bilinear(&self, x: Float, y: Float) -> Option<Color> {
+        let max_x = self.dim.x as UnderlyingFloat - 1.0;
+        let max_y = self.dim.y as UnderlyingFloat - 1.0;
+        if x < Float(0.0) || y < Float(0.0) || x > Float(max_x) || y > Float(max_y) {
+            return None;
+        }
+        let (x, y) = (x.0.clamp(0.0, max_x), y.0.clamp(0.0, max_y));
+        let (x0, y0) = (x.floor() as usize, y.floor() as usize);
+        let (x1, y1) = ((x0 + 1).min(self.dim.x - 1), (y0 + 1).min(self.dim.y - 1));
+        let (tx, ty) = (
+            Float(x - x0 as UnderlyingFloat),
+            Float(y - y0 as UnderlyingFloat),
+        );
+        let top = self[Coord2D::new(x0, y0)].lerp(self[Coord2D::new(x1, y0)], tx);
+        let bottom = self[Coord2D::new(x0, y1)].lerp(self[Coord2D::new(x1, y1)], tx);
+        Some(top.lerp(bottom, ty))
+    }
+
+    /// Warps the canvas by the affine transform m into a canvas of dim
+    ///
+    /// Pixel coordinates are treated as points in the xy plane. Each destination pixel
+    /// samples the source at its coordinate mapped through the inverse of m. Pixels sampling
+    /// outside the source, and all pixels for a singular m, are black.
+    pub fn warp_affine(&self, m: &Matrix4, dim: Coord2D) -> Canvas {
+        let mut res = Canvas::new(dim);
+        let Some(inverse) = m.inverse() else {
+            return res;
+        };
+        res.iter_mut().for_each(|(coord, color)| {
+            let p = Tuple::point_from_underlying(
+                coord.x as UnderlyingFloat,
+                coord.y as UnderlyingFloat,
+                0.0,
+            );
+            let source = inverse * p;
+            if let Some(sample) = self.sample_bilinear(source.get_x(), source.get_y()) {
+                *color = sample;
+            }
+        });
+        res
+    }
+
+    /// Adds a weighted sample into a pixel, tracking the accumulated weight
+    ///
+    /// The pixel is not clamped; use resolve_splats to get the weighted average. Coordinates
+    /// outside the canvas are skipped.
+    pub fn splat(&mut self, c: Coord2D, color: Color, weight: Float) {
+        if c.x >= self.dim.x || c.y >= self.dim.y {
+            return;
+        }
+        if self.weights.is_empty() {
+            self.weights = vec![Float(0.0); self.size()];
+        }
+        let index = c.to_index(&self.dim);
+        self.data[index] += color * weight;
+        self.weights[index] += weight;
+    }
+
+    /// Divides every splatted pixel by its accumulated weight
+    ///
+    /// Pixels without accumulated weight are black.
+    ///
+    /// ```
+    /// use rtlib::canvas::Canvas;
+    /// use rtlib::canvas::coord_2d::Coord2D;
+    /// use rtlib::color::Color;
+    /// use rtlib::float::Float;
+    /// let mut canvas = Canvas::new(Coord2D::new(2, 1));
+    /// canvas.splat(Coord2D::new(0, 0), Color::new_from_underlying(0.5, 0.5, 0.5), Float(2.0));
+    /// let resolved = canvas.resolve_splats();
+    /// assert_eq!(resolved[Coord2D::new(0, 0)], Color::new_from_underlying(0.5, 0.5, 0.5));
+    /// assert_eq!(resolved[Coord2D::new(1, 0)], Color::new_from_underlying(0.0, 0.0, 0.0));
+    /// ```
+    pub fn resolve_splats(&self) -> Canvas {
+        let mut res = Canvas::new(self.dim);
+        for (index, weight) in self.weights.iter().enumerate() {
+            if *weight != 0.0 {
+                res[index] = self.data[index] * (Float(1.0) / *weight);
+            }
+        }
+        res
+    }
+
+    /// Creates a canvas of the given dimension by repeating self with wrap-around indexing
+    ///
+    /// An empty source yields a black canvas.
+    pub fn tiled(&self, dim: Coord2D) -> Canvas {
+        let mut res = Canvas::new(dim);
+        if self.size() == 0 {
+            return res;
+        }
+        res.iter_mut().for_each(|(coord, color)| {
+            *color = self[Coord2D::new(coord.x % self.dim.x, coord.y % self.dim.y)];
+        });
+        res
+    }
+
+    /// Writes a single row or column as CSV with position, channels and luminance
+    ///
+    /// ```
+    /// use rtlib::canvas::{Axis, Canvas};
+    /// use rtlib::canvas::coord_2d::Coord2D;
+    /// let canvas = Canvas::new(Coord2D::new(2, 2));
+    /// let mut data: Vec<u8> = Vec::new();
+    /// canvas.scanline_csv(Axis::Column, 1, &mut data).unwrap();
+    /// assert_eq!(std::str::from_utf8(&data).unwrap(), "position,r,g,b,luminance\n0,0,0,0,0\n1,0,0,0,0\n");
+    /// assert!(canvas.scanline_csv(Axis::Row, 2, &mut data).is_err());
+    /// ```
+    pub fn scanline_csv(
+        &self,
+        axis: Axis,
+        index: usize,
+        w: &mut impl std::io::Write,
+    ) -> Result<(), std::io::Error> {
+        let (limit, length) = match axis {
+            Axis::Row => (self.dim.y, self.dim.x),
+            Axis::Column => (self.dim.x, self.dim.y),
+        };
+        if index >= limit {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("scanline {} out of range for {}", index, self.dim),
+            ));
+        }
+        writeln!(w, "position,r,g,b,luminance")?;
+        for position in 0..length {
+            let coord = match axis {
+                Axis::Row => Coord2D::new(position, index),
+                Axis::Column => Coord2D::new(index, position),
+            };
+            let color = self[coord];
+            writeln!(
+                w,
+                "{},{},{},{},{}",
+                position,
+                color.get_r(),
+                color.get_g(),
+                color.get_b(),
+                color.luminance()
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Downscales to dim by averaging the covered source pixels in linear space
+    ///
+    /// Channels are decoded with the given gamma before averaging and re-encoded afterwards.
+    /// A gamma of 1 yields a plain box average.
+    pub fn downscale_linear(&self, dim: Coord2D, gamma: Float) -> Canvas {
+        let mut res = Canvas::new(dim);
+        let decode = |f: Float| Float(f.0.max(0.0).powf(gamma.0));
+        let encode = |f: Float| Float(f.0.powf(1.0 / gamma.0));
+        let range = |dst: usize, dst_len: usize, src_len: usize| {
+            let start = dst * src_len / dst_len;
+            let end = ((dst + 1) * src_len / dst_len).max(start + 1).min(src_len);
+            start..end
+        };
+        res.iter_mut().for_each(|(coord, color)| {
+            let mut sum = Color::new_from_underlying(0.0, 0.0, 0.0);
+            let mut count = 0;
+            for y in range(coord.y, dim.y, self.dim.y) {
+                for x in range(coord.x, dim.x, self.dim.x) {
+                    let src = self[Coord2D::new(x, y)];
+                    sum += Color::new(
+                        decode(src.get_r()),
+                        decode(src.get_g()),
+                        decode(src.get_b()),
+                    );
+                    count += 1;
+                }
+            }
+            if count > 0 {
+                let avg = sum * (1.0 / count as UnderlyingFloat);
+                *color = Color::new(
+                    encode(avg.get_r()),
+                    encode(avg.get_g()),
+                    encode(avg.get_b()),
+                );
+            }
+        });
+        res
+    }
+
+    /// Finds the bounding box of all non-black pixels as top left corner and dimension
+    ///
+    /// Returns None if every pixel is black.
+    pub fn content_bounds(&self) -> Option<(Coord2D, Coord2D)> {
+        let black = Color::new_from_underlying(0.0, 0.0, 0.0);
+        let (min, max) = self.iter().filter(|(_, color)| **color != black).fold(
+            None,
+            |bounds: Option<(Coord2D, Coord2D)>, (c, _)| {
+                Some(match bounds {
+                    None => (c, c),
+                    Some((min, max)) => (
+                        Coord2D::new(min.x.min(c.x), min.y.min(c.y)),
+                        Coord2D::new(max.x.max(c.x), max.y.max(c.y)),
+                    ),
+                })
+            },
+        )?;
+        Some((min, Coord2D::new(max.x - min.x + 1, max.y - min.y + 1)))
+    }
+
+    /// Crops to the non-black content and surrounds it by border pixels of bg
+    ///
+    /// Returns None if every pixel is black.
+    pub fn autocrop(&self, border: usize, bg: Color) -> Option<Canvas> {
+        let (top_left, dim) = self.content_bounds()?;
+        let mut res = Canvas::new(Coord2D::new(dim.x + 2 * border, dim.y + 2 * border));
+        res.iter_mut().for_each(|(coord, color)| {
+            let inside = (border..border + dim.x).contains(&coord.x)
+                && (border..border + dim.y).contains(&coord.y);
+            *color = if inside {
+                self[Coord2D::new(top_left.x + coord.x - border, top_left.y + coord.y - border)]
+            } else {
+                bg
+            };
+        });
+        Some(res)
+    }
+
+    /// Downscales preserving the aspect ratio so the larger dimension equals max_dim
+    ///
+    /// A canvas that already fits is returned unchanged.
+    ///
+    /// ```
+    /// use rtlib::canvas::Canvas;
+    /// use rtlib::canvas::coord_2d::Coord2D;
+    /// let canvas = Canvas::new(Coord2D::new(100, 50));
+    /// assert_eq!(canvas.preview(10).get_dim(), Coord2D::new(10, 5));
+    /// ```
+    pub fn preview(&self, max_dim: usize) -> Canvas {
+        let largest = self.dim.x.max(self.dim.y);
+        if largest <= max_dim {
+            return self.clone();
+        }
+        let scale = |len: usize| ((len * max_dim + largest / 2) / largest).max(1);
+        self.downscale_linear(
+            Coord2D::new(scale(self.dim.x), scale(self.dim.y)),
+            Float(1.0),
+        )
+    }
+
+    /// Darkens the canvas towards the corners
+    ///
+    /// The factor falls off smoothly from 1 at the center to 1 - strength at radius, given as
+    /// a fraction of the half diagonal, and stays there beyond. A radius of zero or less
+    /// darkens every pixel but the exact center.
+    pub fn apply_vignette(&mut self, strength: Float, radius: Float) {
+        let center_x = self.dim.x as UnderlyingFloat / 2.0;
+        let center_y = self.dim.y as UnderlyingFloat / 2.0;
+        let half_diagonal = Float(center_x.hypot(center_y));
+        self.iter_mut().for_each(|(coord, color)| {
+            let dx = Float(coord.x as UnderlyingFloat + 0.5 - center_x);
+            let dy = Float(coord.y as UnderlyingFloat + 0.5 - center_y);
+            let r = dx.hypot(dy) / half_diagonal;
+            let falloff = if radius.0 > 0.0 {
+                r.smoothstep(Float(0.0), radius)
+            } else if r.0 > 0.0 {
+                Float(1.0)
+            } else {
+                Float(0.0)
+            };
+            *color *= Float(1.0) - strength * falloff;
+        });
+    }
+
+    /// Computes luminance and color statistics over a region clamped to the canvas
+    ///
+    /// Returns None if the clamped region is empty.
+    pub fn region_stats(&self, top_left: Coord2D, dim: Coord2D) -> Option<RegionStats> {
+        let x_end = top_left.x.saturating_add(dim.x).min(self.dim.x);
+        let y_end = top_left.y.saturating_add(dim.y).min(self.dim.y);
+        if top_left.x >= x_end || top_left.y >= y_end {
+            return None;
+        }
+        let mut res = RegionStats {
+            min_luminance: Float(UnderlyingFloat::INFINITY),
+            max_luminance: Float(UnderlyingFloat::NEG_INFINITY),
+            mean_luminance: Float(0.0),
+            mean_color: Color::new_from_underlying(0.0, 0.0, 0.0),
+        };
+        for y in top_left.y..y_end {
+            for x in top_left.x..x_end {
+                let color = self[Coord2D::new(x, y)];
+                let luminance = color.luminance();
+                res.min_luminance = Float(res.min_luminance.0.min(luminance.0));
+                res.max_luminance = Float(res.max_luminance.0.max(luminance.0));
+                res.mean_luminance += luminance;
+                res.mean_color += color;
+            }
+        }
+        let count = ((x_end - top_left.x) * (y_end - top_left.y)) as UnderlyingFloat;
+        res.mean_luminance /= Float(count);
+        res.mean_color *= 1.0 / count;
+        Some(res)
+    }
+
+    fn check_dim(&self, other: &Canvas) -> Result<(), DimMismatch> {
+        if self.dim != other.dim {
+            return Err(DimMismatch {
+                expected: self.dim,
+                actual: other.dim,
+            });
+        }
+        Ok(())
+    }
+
+    /// Finds the first pixel in row-major order that differs from other
+    ///
+    /// Yields the coordinate together with the color of self and other.
+    pub fn first_difference(
+        &self,
+        other: &Canvas,
+    ) -> Result<Option<(Coord2D, Color, Color)>, DimMismatch> {
+        self.check_dim(other)?;
+        Ok(self
+            .iter()
+            .zip(other.data.iter())
+            .find(|((_, lhs), rhs)| lhs != rhs)
+            .map(|((coord, lhs), rhs)| (coord, *lhs, *rhs)))
+    }
+
+    /// Visualizes the per-pixel color distance to other, multiplied by scale
+    ///
+    /// Errors from 0 to 1 are mapped from blue over green to red; larger errors stay red.
+    pub fn diff_heatmap(&self, other: &Canvas, scale: Float) -> Result<Canvas, DimMismatch> {
+        self.check_dim(other)?;
+        let blue = Color::new_from_underlying(0.0, 0.0, 1.0);
+        let green = Color::new_from_underlying(0.0, 1.0, 0.0);
+        let red = Color::new_from_underlying(1.0, 0.0, 0.0);
+        let mut res = Canvas::new(self.dim);
+        res.iter_mut().for_each(|(coord, color)| {
+            let error = (self[coord].distance(other[coord]) * scale).clamp(Float(0.0), Float(1.0));
+            *color = if error.0 < 0.5 {
+                blue.lerp(green, error * 2.0)
+            } else {
+                green.lerp(red, error * 2.0 - Float(1.0))
+            };
+        });
+        Ok(res)
+    }
+
+    /// Successively blends each layer over base by its opacity
+    pub fn flatten_layers(
+        base: &Canvas,
+        layers: &[(Canvas, Float)],
+    ) -> Result<Canvas, DimMismatch> {
+        let mut res = base.clone();
+        for (layer, opacity) in layers {
+            res.check_dim(layer)?;
+            res.data
+                .iter_mut()
+                .zip(layer.data.iter())
+                .for_each(|(acc, color)| *acc = acc.lerp(*color, *opacity));
+        }
+        Ok(res)
+    }
+
+    /// Averages a sequence of frames into one motion blurred canvas
+    ///
+    /// All frames must share the dimension of the first; no frames yield an empty canvas.
+    pub fn accumulate_frames(frames: &[Canvas]) -> Result<Canvas, DimMismatch> {
+        let Some((first, rest)) = frames.split_first() else {
+            return Ok(Canvas::new(Coord2D::new(0, 0)));
+        };
+        let mut res = first.clone();
+        for frame in rest {
+            res.check_dim(frame)?;
+            res.data
+                .iter_mut()
+                .zip(frame.data.iter())
+                .for_each(|(acc, color)| *acc += *color);
+        }
+        let scale = 1.0 / frames.len() as UnderlyingFloat;
+        res.data.iter_mut().for_each(|color| *color *= scale);
+        Ok(res)
+    }
+
     fn u8_representation(float: Float) -> u8 {
         ((float.0 * 255.0).round() as i32).clamp(0, 255) as u8
     }
@@ -105,9 +992,17 @@ impl Canvas {
     }
 
     fn write_data(&self, f: &mut impl std::io::Write) -> Result<(), std::io::Error> {
+        self.write_data_transferred(f, |float| float)
+    }
+
+    fn write_data_transferred(
+        &self,
+        f: &mut impl std::io::Write,
+        transfer: impl Fn(Float) -> Float,
+    ) -> Result<(), std::io::Error> {
         let mut line_width = 0;
         let mut do_write = |float: Float, force_reset: bool| -> Result<(), std::io::Error> {
-            let s = format!("{}", Self::u8_representation(float));
+            let s = format!("{}", Self::u8_representation(transfer(float)));
             if force_reset || line_width + 1 + s.len() > 70 {
                 writeln!(f)?;
                 line_width = 0;
@@ -146,16 +1041,420 @@ impl Canvas {
         writeln!(f)
     }
 
-    pub fn write_ppm(&self, f: &mut impl std::io::Write) -> Result<(), std::io::Error> {
-        self.write_header(f)?;
-        self.write_data(f)
-    }
-
-    pub fn write_binary_ppm(&self, f: &mut impl std::io::Write) -> Result<(), std::io::Error> {
-        self.write_binary_header(f)?;
-        self.write_binary_data(f)
-    }
-}
+    fn chamfer_distance(&self, sources: &[bool]) -> Vec<UnderlyingFloat> {
+        let diagonal = std::f64::consts::SQRT_2;
+        let mut dist: Vec<UnderlyingFloat> = sources
+            .iter()
+            .map(|s| if *s { 0.0 } else { UnderlyingFloat::INFINITY })
+            .collect();
+        let (w, h) = (self.dim.x as i64, self.dim.y as i64);
+        let mut relax = |x: i64, y: i64, offsets: &[(i64, i64, UnderlyingFloat)]| {
+            let index = (x + y * w) as usize;
+            for (dx, dy, cost) in offsets {
+                let (nx, ny) = (x + dx, y + dy);
+                if nx >= 0 && ny >= 0 && nx < w && ny < h {
+                    let candidate = dist[(nx + ny * w) as usize] + cost;
+                    if candidate < dist[index] {
+                        dist[index] = candidate;
+                    }
+                }
+            }
+        };
+        let forward = [
+            (-1, 0, 1.0),
+            (-1, -1, diagonal),
+            (0, -1, 1.0),
+            (1, -1, diagonal),
+        ];
+        for y in 0..h {
+            for x in 0..w {
+                relax(x, y, &forward);
+            }
+        }
+        let backward = [
+            (1, 0, 1.0),
+            (1, 1, diagonal),
+            (0, 1, 1.0),
+            (-1, 1, diagonal),
+        ];
+        for y in (0..h).rev() {
+            for x in (0..w).rev() {
+                relax(x, y, &backward);
+            }
+        }
+        dist
+    }
+
+    /// Computes an approximate signed distance field of the shape formed by pixels with
+    /// luminance above threshold
+    ///
+    /// Distances are positive inside and negative outside, scaled so the boundary maps to
+    /// 0.5 gray and the largest absolute distance to black or white.
+    pub fn distance_field(&self, threshold: Float) -> Canvas {
+        let inside: Vec<bool> = self
+            .data
+            .iter()
+            .map(|c| c.luminance().0 > threshold.0)
+            .collect();
+        let outside: Vec<bool> = inside.iter().map(|i| !i).collect();
+        let mut res = Canvas::new(self.dim);
+        if inside.iter().all(|i| *i) || outside.iter().all(|o| *o) {
+            let value = if inside.first() == Some(&true) {
+                1.0
+            } else {
+                0.0
+            };
+            res.data
+                .iter_mut()
+                .for_each(|c| *c = Color::new_from_underlying(value, value, value));
+            return res;
+        }
+        let to_outside = self.chamfer_distance(&outside);
+        let to_inside = self.chamfer_distance(&inside);
+        let signed: Vec<UnderlyingFloat> = to_outside
+            .iter()
+            .zip(to_inside.iter())
+            .map(|(o, i)| o - i)
+            .collect();
+        let max_abs = signed
+            .iter()
+            .fold(0.0, |m: UnderlyingFloat, s| m.max(s.abs()));
+        res.data.iter_mut().zip(signed).for_each(|(c, s)| {
+            let value = 0.5 + s / (2.0 * max_abs);
+            *c = Color::new_from_underlying(value, value, value);
+        });
+        res
+    }
+
+    /// Creates a grayscale alpha matte keying out colors near key
+    ///
+    /// Pixels within tolerance of key become black (transparent), pixels beyond twice the
+    /// tolerance white (opaque), with a linear falloff in between.
+    pub fn chroma_key(&self, key: Color, tolerance: Float) -> Canvas {
+        let mut res = Canvas::new(self.dim);
+        res.data
+            .iter_mut()
+            .zip(self.data.iter())
+            .for_each(|(matte, color)| {
+                let distance = color.distance(key).0;
+                let alpha = if distance <= tolerance.0 {
+                    0.0
+                } else if distance >= 2.0 * tolerance.0 {
+                    1.0
+                } else {
+                    (distance - tolerance.0) / tolerance.0
+                };
+                *matte = Color::new_from_underlying(alpha, alpha, alpha);
+            });
+        res
+    }
+
+    /// Copies src onto the canvas at the given offset, skipping pixels within tolerance of key
+    ///
+    /// Parts outside the canvas are clipped.
+    pub fn blit_chroma_keyed(&mut self, src: &Canvas, at: Coord2D, key: Color, tolerance: Float) {
+        for (coord, color) in src.iter() {
+            if color.distance(key).0 <= tolerance.0 {
+                continue;
+            }
+            self.plot(Coord2D::new(at.x + coord.x, at.y + coord.y), *color);
+        }
+    }
+
+    /// Converts straight to premultiplied alpha, taking alpha from the luminance of alpha
+    pub fn premultiply_alpha(&mut self, alpha: &Canvas) -> Result<(), DimMismatch> {
+        self.check_dim(alpha)?;
+        self.data
+            .iter_mut()
+            .zip(alpha.data.iter())
+            .for_each(|(color, a)| *color = color.premultiply(a.luminance()));
+        Ok(())
+    }
+
+    /// Converts premultiplied to straight alpha, taking alpha from the luminance of alpha
+    ///
+    /// Pixels with zero alpha become black.
+    pub fn unpremultiply_alpha(&mut self, alpha: &Canvas) -> Result<(), DimMismatch> {
+        self.check_dim(alpha)?;
+        self.data
+            .iter_mut()
+            .zip(alpha.data.iter())
+            .for_each(|(color, a)| *color = color.unpremultiply(a.luminance()));
+        Ok(())
+    }
+
+    fn box_average(&self, c: Coord2D, radius: usize) -> Color {
+        let x_range = c.x.saturating_sub(radius)..(c.x + radius + 1).min(self.dim.x);
+        let y_range = c.y.saturating_sub(radius)..(c.y + radius + 1).min(self.dim.y);
+        let count = (x_range.len() * y_range.len()) as UnderlyingFloat;
+        let mut sum = Color::new_from_underlying(0.0, 0.0, 0.0);
+        for y in y_range {
+            for x in x_range.clone() {
+                sum += self[Coord2D::new(x, y)];
+            }
+        }
+        sum * (1.0 / count)
+    }
+
+    /// Box-blurs each pixel with a radius scaled by the luminance of mask at that pixel
+    ///
+    /// A luminance of 0 leaves the pixel untouched, 1 blurs it with the full radius.
+    pub fn masked_blur(&self, mask: &Canvas, radius: usize) -> Result<Canvas, DimMismatch> {
+        self.check_dim(mask)?;
+        let mut res = self.clone();
+        res.iter_mut().for_each(|(coord, color)| {
+            let amount = mask[coord].luminance().clamp(Float(0.0), Float(1.0));
+            let r = (amount * radius as UnderlyingFloat).0.round() as usize;
+            if r > 0 {
+                *color = self.box_average(coord, r);
+            }
+        });
+        Ok(res)
+    }
+
+    /// Sharpens by adding the difference to a box blur scaled by amount, clamped to [0, 1]
+    pub fn unsharp_mask(&self, radius: usize, amount: Float) -> Canvas {
+        let mut res = self.clone();
+        res.iter_mut().for_each(|(coord, color)| {
+            let blurred = self.box_average(coord, radius);
+            *color += (*color - blurred) * amount;
+            color.elements.iter_mut().for_each(Float::saturate_assign);
+        });
+        res
+    }
+
+    /// Writes the canvas as NetPBM PAM
+    ///
+    /// With an alpha canvas of matching dimension the output is RGB_ALPHA, taking alpha from
+    /// the luminance of each alpha pixel; otherwise it is RGB.
+    pub fn write_pam(
+        &self,
+        alpha: Option<&Canvas>,
+        w: &mut impl std::io::Write,
+    ) -> Result<(), std::io::Error> {
+        if let Some(alpha) = alpha {
+            self.check_dim(alpha)
+                .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?;
+        }
+        let (depth, tuple_type) = match alpha {
+            Some(_) => (4, "RGB_ALPHA"),
+            None => (3, "RGB"),
+        };
+        write!(
+            w,
+            "P7\nWIDTH {}\nHEIGHT {}\nDEPTH {}\nMAXVAL 255\nTUPLTYPE {}\nENDHDR\n",
+            self.dim.x, self.dim.y, depth, tuple_type
+        )?;
+        let mut data = Vec::with_capacity(self.size() * depth);
+        for (index, color) in self.data.iter().enumerate() {
+            data.push(Self::u8_representation(color.get_r()));
+            data.push(Self::u8_representation(color.get_g()));
+            data.push(Self::u8_representation(color.get_b()));
+            if let Some(alpha) = alpha {
+                data.push(Self::u8_representation(alpha.data[index].luminance()));
+            }
+        }
+        w.write_all(&data)
+    }
+
+    pub fn write_ppm(&self, f: &mut impl std::io::Write) -> Result<(), std::io::Error> {
+        self.write_header(f)?;
+        self.write_data(f)
+    }
+
+    /// Writes the canvas as PPM with every channel encoded by the sRGB transfer function
+    pub fn write_ppm_srgb(&self, f: &mut impl std::io::Write) -> Result<(), std::io::Error> {
+        self.write_header(f)?;
+        self.write_data_transferred(f, Float::srgb_encode)
+    }
+
+    /// Extracts one channel (0 = r, 1 = g, 2 = b) as a grayscale canvas
+    ///
+    /// Panics if the channel is out of range.
+    pub fn channel(&self, channel: usize) -> Canvas {
+        let mut res = Canvas::new(self.dim);
+        res.iter_mut().for_each(|(coord, color)| {
+            let v = self[coord].elements[channel];
+            *color = Color::new(v, v, v);
+        });
+        res
+    }
+
+    /// Writes each channel as grayscale PPM to prefix_r.ppm, prefix_g.ppm and prefix_b.ppm in dir
+    pub fn save_channels<P: AsRef<std::path::Path>>(
+        &self,
+        dir: P,
+        prefix: &str,
+    ) -> Result<(), std::io::Error> {
+        for (channel, suffix) in ["r", "g", "b"].into_iter().enumerate() {
+            let path = dir.as_ref().join(format!("{}_{}.ppm", prefix, suffix));
+            let mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+            self.channel(channel).write_ppm(&mut file)?;
+            std::io::Write::flush(&mut file)?;
+        }
+        Ok(())
+    }
+
+    pub fn write_binary_ppm(&self, f: &mut impl std::io::Write) -> Result<(), std::io::Error> {
+        self.write_binary_header(f)?;
+        self.write_binary_data(f)
+    }
+
+    /// Reads the next whitespace separated header token, skipping # comments
+    ///
+    /// Exactly one whitespace byte after the token is consumed.
+    fn read_ppm_token(r: &mut impl std::io::Read) -> Result<String, PpmError> {
+        let mut token = Vec::new();
+        let mut in_comment = false;
+        for byte in std::io::Read::bytes(&mut *r) {
+            let byte = byte?;
+            if in_comment {
+                in_comment = byte != b'\n' && byte != b'\r';
+            } else if byte.is_ascii_whitespace() {
+                if !token.is_empty() {
+                    break;
+                }
+            } else if byte == b'#' && token.is_empty() {
+                in_comment = true;
+            } else {
+                token.push(byte);
+            }
+        }
+        if token.is_empty() {
+            return Err(PpmError::BadHeader("unexpected end of header".to_string()));
+        }
+        Ok(String::from_utf8_lossy(&token).into_owned())
+    }
+
+    fn read_ppm_header(
+        r: &mut impl std::io::Read,
+        magic: &str,
+    ) -> Result<(Coord2D, u32), PpmError> {
+        let found = Self::read_ppm_token(r)?;
+        if found != magic {
+            return Err(PpmError::BadMagic(found));
+        }
+        let mut number = |name: &str| -> Result<u32, PpmError> {
+            let token = Self::read_ppm_token(r)?;
+            token
+                .parse::<u32>()
+                .map_err(|_| PpmError::BadHeader(format!("invalid {} {:?}", name, token)))
+        };
+        let width = number("width")?;
+        let height = number("height")?;
+        let maxval = number("maxval")?;
+        if maxval == 0 || maxval > 65535 {
+            return Err(PpmError::BadHeader(format!(
+                "maxval {} out of range",
+                maxval
+            )));
+        }
+        Ok((Coord2D::new(width as usize, height as usize), maxval))
+    }
+
+    fn ppm_sample_count(dim: Coord2D) -> Result<usize, PpmError> {
+        dim.x
+            .checked_mul(dim.y)
+            .and_then(|pixels| pixels.checked_mul(3))
+            .ok_or_else(|| PpmError::BadHeader(format!("size {} too large", dim)))
+    }
+
+    /// Builds the canvas only once the samples are known to match the header, so a lying
+    /// header cannot make it allocate more than the data provides
+    fn from_ppm_samples(
+        dim: Coord2D,
+        maxval: u32,
+        samples: impl IntoIterator<Item = u64>,
+    ) -> Result<Canvas, PpmError> {
+        let expected = Self::ppm_sample_count(dim)?;
+        let mut values = Vec::new();
+        let mut actual = 0;
+        for sample in samples {
+            if sample > maxval as u64 {
+                return Err(PpmError::SampleOverflow { sample, maxval });
+            }
+            if actual < expected {
+                values.push(Float(sample as UnderlyingFloat / maxval as UnderlyingFloat));
+            }
+            actual += 1;
+        }
+        if actual != expected {
+            return Err(PpmError::SampleCount { expected, actual });
+        }
+        let mut canvas = Canvas::new(dim);
+        for (color, rgb) in canvas.data.iter_mut().zip(values.chunks_exact(3)) {
+            *color = Color::new(rgb[0], rgb[1], rgb[2]);
+        }
+        Ok(canvas)
+    }
+
+    /// Reads a plain P3 PPM, normalizing samples by maxval
+    ///
+    /// ```
+    /// use rtlib::canvas::Canvas;
+    /// use rtlib::color::Color;
+    /// let mut data: &[u8] = b"P3\n# comment\n2 1\n255\n255 0 51\n  0 255 0\n";
+    /// let canvas = Canvas::read_ppm(&mut data).unwrap();
+    /// assert_eq!(canvas[0], Color::new_from_underlying(1.0, 0.0, 0.2));
+    /// assert_eq!(canvas[1], Color::new_from_underlying(0.0, 1.0, 0.0));
+    /// ```
+    pub fn read_ppm(r: &mut impl std::io::BufRead) -> Result<Canvas, PpmError> {
+        let (dim, maxval) = Self::read_ppm_header(r, "P3")?;
+        let mut samples = Vec::new();
+        for line in std::io::BufRead::lines(&mut *r) {
+            let line = line?;
+            let content = line.split('#').next().unwrap_or("");
+            for token in content.split_ascii_whitespace() {
+                // samples too large even for u64 still count as overflowing maxval
+                let sample = match token.parse::<u64>() {
+                    Ok(sample) => sample,
+                    Err(e) if *e.kind() == std::num::IntErrorKind::PosOverflow => u64::MAX,
+                    Err(_) => return Err(PpmError::InvalidSample(token.to_string())),
+                };
+                samples.push(sample);
+            }
+        }
+        Self::from_ppm_samples(dim, maxval, samples)
+    }
+
+    /// Reads a binary P6 PPM, normalizing samples by maxval
+    ///
+    /// Samples are single bytes for maxval below 256 and big endian pairs otherwise. Exactly
+    /// the samples announced by the header are read, anything after them is left in r.
+    ///
+    /// ```
+    /// use rtlib::canvas::Canvas;
+    /// use rtlib::color::Color;
+    /// let mut data: &[u8] = b"P6\n1 1\n255\n\xff\x00\x33";
+    /// let canvas = Canvas::read_binary_ppm(&mut data).unwrap();
+    /// assert_eq!(canvas[0], Color::new_from_underlying(1.0, 0.0, 0.2));
+    /// ```
+    pub fn read_binary_ppm(r: &mut impl std::io::Read) -> Result<Canvas, PpmError> {
+        let (dim, maxval) = Self::read_ppm_header(r, "P6")?;
+        let bytes_per_sample = if maxval < 256 { 1 } else { 2 };
+        let expected = Self::ppm_sample_count(dim)?;
+        let expected_bytes = expected
+            .checked_mul(bytes_per_sample)
+            .ok_or_else(|| PpmError::BadHeader(format!("size {} too large", dim)))?;
+        let mut data = Vec::new();
+        std::io::Read::read_to_end(
+            &mut std::io::Read::take(&mut *r, expected_bytes as u64),
+            &mut data,
+        )?;
+        if data.len() != expected_bytes {
+            return Err(PpmError::SampleCount {
+                expected,
+                actual: data.len() / bytes_per_sample,
+            });
+        }
+        let samples = data.chunks_exact(bytes_per_sample).map(|chunk| {
+            chunk
+                .iter()
+                .fold(0u64, |sample, byte| (sample << 8) | *byte as u64)
+        });
+        Self::from_ppm_samples(dim, maxval, samples)
+    }
+}
 
 impl Index<usize> for Canvas {
     type Output = Color;
@@ -206,6 +1505,518 @@ mod tests {
         });
     }
 
+    #[test]
+    fn fill_and_clear() {
+        let mut canvas = Canvas::new(Coord2D::new(4, 3));
+        let red = Color::new_from_underlying(1.0, 0.0, 0.0);
+        canvas.fill(red);
+        assert_eq!(canvas.size(), 12);
+        assert!(canvas.iter().all(|(_, color)| *color == red));
+        canvas.clear();
+        assert_eq!(canvas, Canvas::new(Coord2D::new(4, 3)));
+
+        let white = Color::new_from_underlying(1.0, 1.0, 1.0);
+        let coord = Coord2D::new(1, 1);
+        canvas.splat(coord, red, Float(3.0));
+        canvas.clear();
+        assert_eq!(canvas, Canvas::new(Coord2D::new(4, 3)));
+        canvas.splat(coord, white, Float(1.0));
+        assert_eq!(canvas.resolve_splats()[coord], white);
+    }
+
+    fn line_pixels(canvas: &Canvas) -> Vec<Coord2D> {
+        let black = Color::new_from_underlying(0.0, 0.0, 0.0);
+        canvas
+            .iter()
+            .filter(|(_, color)| **color != black)
+            .map(|(coord, _)| coord)
+            .collect()
+    }
+
+    #[test]
+    fn draw_line() {
+        let red = Color::new_from_underlying(1.0, 0.0, 0.0);
+        let dim = Coord2D::new(5, 5);
+
+        let mut horizontal = Canvas::new(dim);
+        horizontal.draw_line(Coord2D::new(3, 1), Coord2D::new(0, 1), red);
+        assert_eq!(
+            line_pixels(&horizontal),
+            (0..4).map(|x| Coord2D::new(x, 1)).collect::<Vec<_>>()
+        );
+
+        let mut vertical = Canvas::new(dim);
+        vertical.draw_line(Coord2D::new(2, 1), Coord2D::new(2, 4), red);
+        assert_eq!(
+            line_pixels(&vertical),
+            (1..5).map(|y| Coord2D::new(2, y)).collect::<Vec<_>>()
+        );
+
+        let mut diagonal = Canvas::new(dim);
+        diagonal.draw_line(Coord2D::new(4, 4), Coord2D::new(0, 0), red);
+        assert_eq!(
+            line_pixels(&diagonal),
+            (0..5).map(|i| Coord2D::new(i, i)).collect::<Vec<_>>()
+        );
+
+        let mut single = Canvas::new(dim);
+        single.draw_line(Coord2D::new(1, 3), Coord2D::new(1, 3), red);
+        assert_eq!(line_pixels(&single), [Coord2D::new(1, 3)]);
+    }
+
+    #[test]
+    fn draw_line_steep_and_clipped() {
+        let red = Color::new_from_underlying(1.0, 0.0, 0.0);
+        let mut steep = Canvas::new(Coord2D::new(3, 5));
+        steep.draw_line(Coord2D::new(0, 0), Coord2D::new(2, 4), red);
+        assert_eq!(
+            line_pixels(&steep),
+            [
+                Coord2D::new(0, 0),
+                Coord2D::new(1, 1),
+                Coord2D::new(1, 2),
+                Coord2D::new(2, 3),
+                Coord2D::new(2, 4),
+            ]
+        );
+
+        let mut clipped = Canvas::new(Coord2D::new(3, 3));
+        clipped.draw_line(Coord2D::new(0, 0), Coord2D::new(10, 10), red);
+        assert_eq!(
+            line_pixels(&clipped),
+            (0..3).map(|i| Coord2D::new(i, i)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn draw_axes() {
+        let mut canvas = Canvas::new(Coord2D::new(10, 10));
+        let red = Color::new_from_underlying(1.0, 0.0, 0.0);
+        let green = Color::new_from_underlying(0.0, 1.0, 0.0);
+        let black = Color::new_from_underlying(0.0, 0.0, 0.0);
+        canvas.draw_axes(Coord2D::new(2, 3), 20);
+        let mut huge = canvas.clone();
+        huge.draw_axes(Coord2D::new(2, 3), usize::MAX);
+        assert_eq!(huge, canvas);
+        huge.draw_axes(Coord2D::new(20, 3), 5);
+        assert_eq!(huge, canvas);
+        canvas.iter().for_each(|(coord, color)| {
+            assert_eq!(
+                *color,
+                match coord {
+                    Coord2D { x: 2, y } if y >= 3 => green,
+                    Coord2D { x, y: 3 } if x > 2 => red,
+                    _ => black,
+                }
+            );
+        });
+    }
+
+    #[test]
+    fn world_to_screen() {
+        let canvas = Canvas::new(Coord2D::new(10, 5));
+        let map = |x, y| canvas.world_to_screen(Tuple::point_from_underlying(x, y, 0.0));
+        assert_eq!(map(0.0, 0.0), Some(Coord2D::new(0, 4)));
+        assert_eq!(map(9.9, 4.9), Some(Coord2D::new(9, 0)));
+        assert_eq!(map(3.0, 2.5), Some(Coord2D::new(3, 2)));
+        assert_eq!(map(-0.1, 2.0), None);
+        assert_eq!(map(10.0, 2.0), None);
+        assert_eq!(map(3.0, -0.1), None);
+        assert_eq!(map(3.0, 5.0), None);
+    }
+
+    #[test]
+    fn splat_weighted_average() {
+        let mut canvas = Canvas::new(Coord2D::new(3, 3));
+        let coord = Coord2D::new(1, 2);
+        canvas.splat(coord, Color::new_from_underlying(1.0, 0.0, 0.5), Float(1.0));
+        canvas.splat(coord, Color::new_from_underlying(0.0, 1.0, 0.5), Float(3.0));
+        assert_eq!(canvas[coord], Color::new_from_underlying(1.0, 3.0, 2.0));
+        let resolved = canvas.resolve_splats();
+        assert_eq!(resolved[coord], Color::new_from_underlying(0.25, 0.75, 0.5));
+        resolved
+            .iter()
+            .filter(|(c, _)| *c != coord)
+            .for_each(|(_, color)| assert_eq!(*color, Color::new_from_underlying(0.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn splat_out_of_bounds_is_skipped() {
+        let mut canvas = Canvas::new(Coord2D::new(2, 2));
+        let white = Color::new_from_underlying(1.0, 1.0, 1.0);
+        canvas.splat(Coord2D::new(2, 0), white, Float(1.0));
+        canvas.splat(Coord2D::new(0, 2), white, Float(1.0));
+        assert_eq!(canvas, Canvas::new(Coord2D::new(2, 2)));
+        assert_eq!(canvas.resolve_splats(), Canvas::new(Coord2D::new(2, 2)));
+    }
+
+    #[test]
+    fn splat_weights_do_not_affect_equality() {
+        let mut canvas = Canvas::new(Coord2D::new(2, 2));
+        canvas.splat(
+            Coord2D::new(1, 1),
+            Color::new_from_underlying(0.0, 0.0, 0.0),
+            Float(2.0),
+        );
+        assert_eq!(canvas, Canvas::new(Coord2D::new(2, 2)));
+    }
+
+    #[test]
+    fn color_wheel() {
+        let canvas = Canvas::color_wheel(Coord2D::new(20, 20));
+        let diagonal_sat = Float(4.5f64.hypot(4.5) / 10.0);
+        let sample = |x, y, hue| {
+            assert_eq!(
+                canvas[Coord2D::new(x, y)],
+                Color::from_hsv(Float(hue), diagonal_sat, Float(1.0))
+            );
+        };
+        sample(14, 5, 45.0);
+        sample(5, 5, 135.0);
+        sample(5, 14, 225.0);
+        sample(14, 14, 315.0);
+        assert_eq!(
+            canvas[Coord2D::new(0, 0)],
+            Color::new_from_underlying(0.0, 0.0, 0.0)
+        );
+        assert_eq!(
+            canvas[Coord2D::new(19, 19)],
+            Color::new_from_underlying(0.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn scanline_csv() {
+        let mut canvas = Canvas::new(Coord2D::new(3, 2));
+        canvas[Coord2D::new(0, 1)] = Color::new_from_underlying(1.0, 0.0, 0.0);
+        canvas[Coord2D::new(1, 1)] = Color::new_from_underlying(0.0, 1.0, 0.0);
+        canvas[Coord2D::new(2, 1)] = Color::new_from_underlying(0.0, 0.0, 0.5);
+        let mut data: Vec<u8> = Vec::new();
+        let res = canvas.scanline_csv(Axis::Row, 1, &mut data);
+        assert!(res.is_ok());
+        let conv = std::str::from_utf8(&data);
+        assert!(conv.is_ok());
+        let str = conv.unwrap();
+        assert_eq!(
+            str,
+            "position,r,g,b,luminance\n0,1,0,0,0.2126\n1,0,1,0,0.7152\n2,0,0,0.5,0.0361\n"
+        );
+    }
+
+    #[test]
+    fn value_noise_reproducible() {
+        let dim = Coord2D::new(16, 16);
+        let a = Canvas::value_noise(dim, Float(4.0), 42);
+        assert_eq!(a, Canvas::value_noise(dim, Float(4.0), 42));
+        assert_ne!(a, Canvas::value_noise(dim, Float(4.0), 43));
+        a.iter().for_each(|(_, color)| {
+            assert!(color.get_r() >= 0.0 && color.get_r() <= 1.0);
+            assert_eq!(color.get_r(), color.get_g());
+            assert_eq!(color.get_r(), color.get_b());
+        });
+    }
+
+    #[test]
+    fn fbm_noise_reproducible() {
+        let dim = Coord2D::new(16, 16);
+        let a = Canvas::fbm_noise(dim, Float(8.0), 4, 7);
+        assert_eq!(a, Canvas::fbm_noise(dim, Float(8.0), 4, 7));
+        assert_ne!(a, Canvas::fbm_noise(dim, Float(8.0), 4, 8));
+        assert_ne!(a, Canvas::value_noise(dim, Float(8.0), 7));
+        a.iter()
+            .for_each(|(_, color)| assert!(color.get_r() >= 0.0 && color.get_r() <= 1.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "noise scale must be positive")]
+    fn fbm_noise_zero_scale() {
+        Canvas::fbm_noise(Coord2D::new(2, 2), Float(0.0), 1, 7);
+    }
+
+    #[test]
+    fn first_difference() {
+        let mut a = Canvas::new(Coord2D::new(4, 3));
+        a[Coord2D::new(3, 0)] = Color::new_from_underlying(0.5, 0.5, 0.5);
+        let mut b = a.clone();
+        assert_eq!(a.first_difference(&b), Ok(None));
+        let changed = Color::new_from_underlying(0.0, 0.2, 0.0);
+        b[Coord2D::new(1, 2)] = changed;
+        b[Coord2D::new(2, 2)] = changed;
+        assert_eq!(
+            a.first_difference(&b),
+            Ok(Some((
+                Coord2D::new(1, 2),
+                Color::new_from_underlying(0.0, 0.0, 0.0),
+                changed
+            )))
+        );
+        assert_eq!(
+            a.first_difference(&Canvas::new(Coord2D::new(3, 4))),
+            Err(DimMismatch {
+                expected: Coord2D::new(4, 3),
+                actual: Coord2D::new(3, 4)
+            })
+        );
+    }
+
+    #[test]
+    fn downscale_linear_checkerboard() {
+        let mut canvas = Canvas::new(Coord2D::new(4, 4));
+        canvas.iter_mut().for_each(|(coord, color)| {
+            if (coord.x + coord.y) % 2 == 0 {
+                *color = Color::new_from_underlying(1.0, 1.0, 1.0);
+            }
+        });
+        let dim = Coord2D::new(2, 2);
+        let linear = canvas.downscale_linear(dim, Float(2.2));
+        let naive = canvas.downscale_linear(dim, Float(1.0));
+        let encoded_half = 0.5f64.powf(1.0 / 2.2);
+        linear
+            .iter()
+            .zip(naive.iter())
+            .for_each(|((_, l), (_, n))| {
+                assert_eq!(
+                    *l,
+                    Color::new_from_underlying(encoded_half, encoded_half, encoded_half)
+                );
+                assert_eq!(*n, Color::new_from_underlying(0.5, 0.5, 0.5));
+                assert!(n.get_r() < l.get_r());
+            });
+    }
+
+    #[test]
+    fn downscale_linear_uneven() {
+        let mut canvas = Canvas::new(Coord2D::new(3, 1));
+        canvas[0] = Color::new_from_underlying(0.3, 0.3, 0.3);
+        canvas[1] = Color::new_from_underlying(0.6, 0.6, 0.6);
+        canvas[2] = Color::new_from_underlying(0.9, 0.9, 0.9);
+        let res = canvas.downscale_linear(Coord2D::new(2, 1), Float(1.0));
+        assert_eq!(res[0], Color::new_from_underlying(0.3, 0.3, 0.3));
+        assert_eq!(res[1], Color::new_from_underlying(0.75, 0.75, 0.75));
+    }
+
+    #[test]
+    fn flatten_layers() {
+        let dim = Coord2D::new(3, 2);
+        let base = Canvas::new(dim);
+        let mut white = Canvas::new(dim);
+        white
+            .iter_mut()
+            .for_each(|(_, color)| *color = Color::new_from_underlying(1.0, 1.0, 1.0));
+        let mut red = Canvas::new(dim);
+        red.iter_mut()
+            .for_each(|(_, color)| *color = Color::new_from_underlying(1.0, 0.0, 0.0));
+
+        let gray = Canvas::flatten_layers(&base, &[(white.clone(), Float(0.5))]).unwrap();
+        gray.iter()
+            .for_each(|(_, color)| assert_eq!(*color, Color::new_from_underlying(0.5, 0.5, 0.5)));
+
+        let res = Canvas::flatten_layers(&base, &[(white, Float(0.5)), (red, Float(0.5))]).unwrap();
+        res.iter().for_each(|(_, color)| {
+            assert_eq!(*color, Color::new_from_underlying(0.75, 0.25, 0.25))
+        });
+
+        assert!(
+            Canvas::flatten_layers(&base, &[(Canvas::new(Coord2D::new(2, 3)), Float(0.5))])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn from_fn_with_progress() {
+        let mut reports = Vec::new();
+        let mut evaluated = 0;
+        let canvas = Canvas::from_fn_with_progress(
+            Coord2D::new(3, 4),
+            |coord| {
+                evaluated += 1;
+                Color::new_from_underlying(
+                    coord.x as UnderlyingFloat,
+                    coord.y as UnderlyingFloat,
+                    0.0,
+                )
+            },
+            |done, total| reports.push((done, total)),
+        );
+        assert_eq!(evaluated, 12);
+        assert_eq!(reports, [(1, 4), (2, 4), (3, 4), (4, 4)]);
+        canvas.iter().for_each(|(coord, color)| {
+            assert_eq!(
+                *color,
+                Color::new_from_underlying(
+                    coord.x as UnderlyingFloat,
+                    coord.y as UnderlyingFloat,
+                    0.0
+                )
+            )
+        });
+    }
+
+    #[test]
+    fn draw_line_dashed() {
+        let red = Color::new_from_underlying(1.0, 0.0, 0.0);
+        let mut canvas = Canvas::new(Coord2D::new(12, 3));
+        canvas.draw_line_dashed(Coord2D::new(1, 1), Coord2D::new(20, 1), red, 3, 2);
+        let row: String = (0..12)
+            .map(|x| match canvas[Coord2D::new(x, 1)] {
+                c if c == red => '#',
+                _ => '.',
+            })
+            .collect();
+        assert_eq!(row, ".###..###..#");
+        assert!(canvas
+            .iter()
+            .filter(|(coord, _)| coord.y != 1)
+            .all(|(_, color)| *color == Color::new_from_underlying(0.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn line_coords_octants() {
+        let line = |a: (usize, usize), b: (usize, usize)| {
+            Canvas::line_coords(Coord2D::new(a.0, a.1), Coord2D::new(b.0, b.1))
+                .map(|c| (c.x, c.y))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(line((0, 0), (3, 1)), [(0, 0), (1, 0), (2, 1), (3, 1)]);
+        assert_eq!(line((3, 1), (0, 0)), [(3, 1), (2, 1), (1, 0), (0, 0)]);
+        assert_eq!(line((1, 0), (0, 3)), [(1, 0), (1, 1), (0, 2), (0, 3)]);
+        assert_eq!(line((2, 2), (2, 2)), [(2, 2)]);
+    }
+
+    #[test]
+    fn flood_fill_tolerance() {
+        let blob = || {
+            let mut canvas = Canvas::new(Coord2D::new(7, 7));
+            canvas.iter_mut().for_each(|(coord, color)| {
+                let ring = coord.x.abs_diff(3).max(coord.y.abs_diff(3));
+                *color = match ring {
+                    0 | 1 => Color::new_from_underlying(1.0, 1.0, 1.0),
+                    2 => Color::new_from_underlying(0.6, 0.6, 0.6),
+                    _ => Color::new_from_underlying(0.0, 0.0, 0.0),
+                };
+            });
+            canvas
+        };
+        let red = Color::new_from_underlying(1.0, 0.0, 0.0);
+        let filled = |canvas: &Canvas| canvas.iter().filter(|(_, c)| **c == red).count();
+
+        let mut canvas = blob();
+        canvas.flood_fill_tolerance(Coord2D::new(3, 3), red, Float(0.5));
+        assert_eq!(filled(&canvas), 9);
+
+        let mut canvas = blob();
+        canvas.flood_fill_tolerance(Coord2D::new(3, 3), red, Float(0.8));
+        assert_eq!(filled(&canvas), 25);
+        assert_eq!(
+            canvas[Coord2D::new(0, 0)],
+            Color::new_from_underlying(0.0, 0.0, 0.0)
+        );
+
+        let mut canvas = blob();
+        canvas.flood_fill_tolerance(Coord2D::new(3, 3), red, Float(2.0));
+        assert_eq!(filled(&canvas), 49);
+    }
+
+    #[test]
+    fn apply_vignette() {
+        let white = Color::new_from_underlying(1.0, 1.0, 1.0);
+        let mut canvas = Canvas::new(Coord2D::new(10, 10));
+        canvas.iter_mut().for_each(|(_, color)| *color = white);
+        canvas.apply_vignette(Float(0.8), Float(0.9));
+        let center = canvas[Coord2D::new(5, 5)].get_r();
+        let edge = canvas[Coord2D::new(9, 5)].get_r();
+        let corner = canvas[Coord2D::new(0, 0)].get_r();
+        assert!(center > 0.95);
+        assert!(center > edge);
+        assert!(edge > corner);
+        assert_eq!(corner, 0.2);
+        assert_eq!(canvas[Coord2D::new(9, 9)], canvas[Coord2D::new(0, 0)]);
+    }
+
+    #[test]
+    fn apply_vignette_zero_radius() {
+        let white = Color::new_from_underlying(1.0, 1.0, 1.0);
+        let mut canvas = Canvas::new(Coord2D::new(3, 3));
+        canvas.fill(white);
+        canvas.apply_vignette(Float(0.5), Float(0.0));
+        assert_eq!(canvas[Coord2D::new(1, 1)], white);
+        assert_eq!(
+            canvas[Coord2D::new(0, 1)],
+            Color::new_from_underlying(0.5, 0.5, 0.5)
+        );
+        assert!(canvas.iter().all(|(_, color)| !color.get_r().0.is_nan()));
+    }
+
+    #[test]
+    fn region_stats() {
+        let mut canvas = Canvas::new(Coord2D::new(4, 4));
+        canvas.iter_mut().for_each(|(coord, color)| {
+            *color = Color::new_from_underlying(
+                coord.x as UnderlyingFloat / 3.0,
+                coord.y as UnderlyingFloat / 3.0,
+                0.0,
+            )
+        });
+        let stats = canvas
+            .region_stats(Coord2D::new(1, 1), Coord2D::new(2, 2))
+            .unwrap();
+        let weight = 0.2126 + 0.7152;
+        assert_eq!(stats.min_luminance, weight / 3.0);
+        assert_eq!(stats.max_luminance, weight * 2.0 / 3.0);
+        assert_eq!(stats.mean_luminance, weight / 2.0);
+        assert_eq!(stats.mean_color, Color::new_from_underlying(0.5, 0.5, 0.0));
+
+        let clamped = canvas
+            .region_stats(Coord2D::new(3, 3), Coord2D::new(5, 5))
+            .unwrap();
+        assert_eq!(
+            clamped.mean_color,
+            Color::new_from_underlying(1.0, 1.0, 0.0)
+        );
+        assert_eq!(clamped.min_luminance, clamped.max_luminance);
+
+        assert_eq!(
+            canvas.region_stats(Coord2D::new(4, 0), Coord2D::new(2, 2)),
+            None
+        );
+        assert_eq!(
+            canvas.region_stats(Coord2D::new(0, 0), Coord2D::new(0, 2)),
+            None
+        );
+    }
+
+    #[test]
+    fn fill_triangle_gouraud() {
+        let red = Color::new_from_underlying(1.0, 0.0, 0.0);
+        let green = Color::new_from_underlying(0.0, 1.0, 0.0);
+        let blue = Color::new_from_underlying(0.0, 0.0, 1.0);
+        let black = Color::new_from_underlying(0.0, 0.0, 0.0);
+        let mut canvas = Canvas::new(Coord2D::new(8, 8));
+        canvas.fill_triangle_gouraud([
+            (Coord2D::new(0, 0), red),
+            (Coord2D::new(9, 0), green),
+            (Coord2D::new(0, 9), blue),
+        ]);
+        let third = 1.0 / 3.0;
+        assert_eq!(
+            canvas[Coord2D::new(3, 3)],
+            Color::new_from_underlying(third, third, third)
+        );
+        assert_eq!(
+            canvas[Coord2D::new(0, 0)],
+            Color::new_from_underlying(1.0, 0.0, 0.0)
+        );
+        assert_eq!(canvas[Coord2D::new(7, 7)], black);
+
+        let mut degenerate = Canvas::new(Coord2D::new(8, 8));
+        degenerate.fill_triangle_gouraud([
+            (Coord2D::new(0, 0), red),
+            (Coord2D::new(3, 3), green),
+            (Coord2D::new(6, 6), blue),
+        ]);
+        assert_eq!(degenerate, Canvas::new(Coord2D::new(8, 8)));
+    }
+
     #[test]
     fn write_header() {
         let canvas = Canvas::new(Coord2D::new(5, 3));
@@ -257,6 +2068,431 @@ mod tests {
         );
     }
 
+    #[test]
+    fn distance_field() {
+        let mut canvas = Canvas::new(Coord2D::new(9, 9));
+        canvas.iter_mut().for_each(|(coord, color)| {
+            if (2..7).contains(&coord.x) && (2..7).contains(&coord.y) {
+                *color = Color::new_from_underlying(1.0, 1.0, 1.0);
+            }
+        });
+        let field = canvas.distance_field(Float(0.5));
+        let at = |x, y| field[Coord2D::new(x, y)].get_r();
+        assert!(at(4, 4) > at(3, 4));
+        assert!(at(3, 4) > at(2, 4));
+        assert!(at(2, 4) > 0.5);
+        assert!(at(1, 4) < 0.5);
+        assert!(at(0, 4) < at(1, 4));
+        assert_eq!(at(4, 4), at(4, 2) + Float(2.0 / 6.0));
+        assert_eq!(at(2, 4), at(6, 4));
+
+        let empty = Canvas::new(Coord2D::new(3, 3)).distance_field(Float(0.5));
+        assert_eq!(empty, Canvas::new(Coord2D::new(3, 3)));
+    }
+
+    #[test]
+    fn chroma_key() {
+        let green = Color::new_from_underlying(0.0, 1.0, 0.0);
+        let mut canvas = Canvas::new(Coord2D::new(4, 1));
+        canvas[0] = green;
+        canvas[1] = Color::new_from_underlying(0.05, 0.95, 0.0);
+        canvas[2] = Color::new_from_underlying(0.0, 0.85, 0.0);
+        canvas[3] = Color::new_from_underlying(1.0, 0.0, 0.0);
+        let matte = canvas.chroma_key(green, Float(0.1));
+        let gray = |v| Color::new_from_underlying(v, v, v);
+        assert_eq!(matte[0], gray(0.0));
+        assert_eq!(matte[1], gray(0.0));
+        assert_eq!(matte[2], gray(0.5));
+        assert_eq!(matte[3], gray(1.0));
+    }
+
+    #[test]
+    fn masked_blur() {
+        let dim = Coord2D::new(8, 1);
+        let mut canvas = Canvas::new(dim);
+        let mut mask = Canvas::new(dim);
+        for x in 0..dim.x {
+            let v = (x as UnderlyingFloat / 7.0).powi(2);
+            canvas[x] = Color::new_from_underlying(v, v, v);
+            if x < 4 {
+                mask[x] = Color::new_from_underlying(1.0, 1.0, 1.0);
+            }
+        }
+        let blurred = canvas.masked_blur(&mask, 1).unwrap();
+        for x in 1..4 {
+            assert_ne!(blurred[x], canvas[x]);
+        }
+        for x in 4..8 {
+            assert_eq!(blurred[x], canvas[x]);
+        }
+        let v = 5.0 / 147.0;
+        assert_eq!(blurred[1], Color::new_from_underlying(v, v, v));
+        assert_eq!(
+            canvas.masked_blur(&Canvas::new(Coord2D::new(1, 1)), 1),
+            Err(DimMismatch {
+                expected: dim,
+                actual: Coord2D::new(1, 1)
+            })
+        );
+    }
+
+    #[test]
+    fn unsharp_mask() {
+        let mut canvas = Canvas::new(Coord2D::new(4, 1));
+        let gray = |v| Color::new_from_underlying(v, v, v);
+        for x in 0..4 {
+            canvas[x] = gray(if x < 2 { 0.2 } else { 0.8 });
+        }
+        let sharpened = canvas.unsharp_mask(1, Float(1.0));
+        assert_eq!(sharpened[1], gray(0.0));
+        assert_eq!(sharpened[2], gray(1.0));
+        assert!(
+            sharpened[2].luminance() - sharpened[1].luminance()
+                > canvas[2].luminance() - canvas[1].luminance()
+        );
+        assert_eq!(sharpened[0], gray(0.2));
+        assert_eq!(canvas.unsharp_mask(1, Float(0.0)), canvas);
+    }
+
+    #[test]
+    fn from_rows() {
+        let color = |v| Color::new_from_underlying(v, 0.0, 0.0);
+        let canvas = Canvas::from_rows(vec![
+            vec![color(0.0), color(0.1), color(0.2)],
+            vec![color(0.3), color(0.4), color(0.5)],
+        ])
+        .unwrap();
+        assert_eq!(canvas.get_dim(), Coord2D::new(3, 2));
+        assert_eq!(canvas[Coord2D::new(2, 0)], color(0.2));
+        assert_eq!(canvas[Coord2D::new(0, 1)], color(0.3));
+        assert_eq!(
+            Canvas::from_rows(std::iter::empty()).unwrap(),
+            Canvas::new(Coord2D::new(0, 0))
+        );
+    }
+
+    #[test]
+    fn from_rows_ragged() {
+        let black = Color::new_from_underlying(0.0, 0.0, 0.0);
+        let res = Canvas::from_rows(vec![vec![black; 3], vec![black; 3], vec![black; 2]]);
+        assert_eq!(
+            res,
+            Err(RaggedRows {
+                row: 2,
+                expected: 3,
+                actual: 2
+            })
+        );
+        assert_eq!(
+            format!("{}", res.unwrap_err()),
+            "row 2 has length 2, expected 3"
+        );
+    }
+
+    #[test]
+    fn content_bounds() {
+        let mut canvas = Canvas::new(Coord2D::new(6, 5));
+        assert_eq!(canvas.content_bounds(), None);
+        canvas[Coord2D::new(3, 1)] = Color::new_from_underlying(0.5, 0.0, 0.0);
+        canvas[Coord2D::new(1, 3)] = Color::new_from_underlying(0.0, 0.0, 0.5);
+        assert_eq!(
+            canvas.content_bounds(),
+            Some((Coord2D::new(1, 1), Coord2D::new(3, 3)))
+        );
+    }
+
+    #[test]
+    fn autocrop() {
+        let red = Color::new_from_underlying(1.0, 0.0, 0.0);
+        let bg = Color::new_from_underlying(0.2, 0.2, 0.2);
+        let mut canvas = Canvas::new(Coord2D::new(10, 8));
+        assert_eq!(canvas.autocrop(1, bg), None);
+        canvas[Coord2D::new(4, 3)] = red;
+        canvas[Coord2D::new(5, 5)] = red;
+        let cropped = canvas.autocrop(2, bg).unwrap();
+        assert_eq!(cropped.get_dim(), Coord2D::new(2 + 2 * 2, 3 + 2 * 2));
+        assert_eq!(cropped[Coord2D::new(2, 2)], red);
+        assert_eq!(cropped[Coord2D::new(3, 4)], red);
+        assert_eq!(
+            cropped[Coord2D::new(3, 2)],
+            Color::new_from_underlying(0.0, 0.0, 0.0)
+        );
+        assert_eq!(cropped[Coord2D::new(0, 0)], bg);
+        assert_eq!(cropped[Coord2D::new(5, 6)], bg);
+    }
+
+    #[test]
+    fn preview() {
+        let mut canvas = Canvas::new(Coord2D::new(100, 50));
+        canvas
+            .iter_mut()
+            .for_each(|(_, color)| *color = Color::new_from_underlying(0.2, 0.4, 0.6));
+        let preview = canvas.preview(10);
+        assert_eq!(preview.get_dim(), Coord2D::new(10, 5));
+        assert_eq!(preview[Coord2D::new(9, 4)], canvas[0]);
+        assert_eq!(
+            Canvas::new(Coord2D::new(50, 1)).preview(10).get_dim(),
+            Coord2D::new(10, 1)
+        );
+        assert_eq!(canvas.preview(100), canvas);
+    }
+
+    #[test]
+    fn accumulate_frames() {
+        let white = Color::new_from_underlying(1.0, 1.0, 1.0);
+        let frames: Vec<Canvas> = (0..3)
+            .map(|x| {
+                let mut frame = Canvas::new(Coord2D::new(3, 1));
+                frame[x] = white;
+                frame
+            })
+            .collect();
+        let composite = Canvas::accumulate_frames(&frames).unwrap();
+        let third = 1.0 / 3.0;
+        for x in 0..3 {
+            assert_eq!(
+                composite[x],
+                Color::new_from_underlying(third, third, third)
+            );
+            assert!(composite[x].luminance() < frames[x][x].luminance());
+        }
+        assert_eq!(Canvas::accumulate_frames(&frames[..1]).unwrap(), frames[0]);
+        assert_eq!(
+            Canvas::accumulate_frames(&[]).unwrap().get_dim(),
+            Coord2D::new(0, 0)
+        );
+        let mismatched = [frames[0].clone(), Canvas::new(Coord2D::new(1, 1))];
+        assert_eq!(
+            Canvas::accumulate_frames(&mismatched),
+            Err(DimMismatch {
+                expected: Coord2D::new(3, 1),
+                actual: Coord2D::new(1, 1)
+            })
+        );
+    }
+
+    #[test]
+    fn diff_heatmap() {
+        let blue = Color::new_from_underlying(0.0, 0.0, 1.0);
+        let mut canvas = Canvas::new(Coord2D::new(3, 2));
+        canvas
+            .iter_mut()
+            .for_each(|(_, color)| *color = Color::new_from_underlying(0.5, 0.5, 0.5));
+        let heatmap = canvas.diff_heatmap(&canvas, Float(10.0)).unwrap();
+        assert!(heatmap.iter().all(|(_, color)| *color == blue));
+
+        let mut altered = canvas.clone();
+        altered[Coord2D::new(1, 1)] = Color::new_from_underlying(0.6, 0.5, 0.5);
+        altered[Coord2D::new(2, 0)] = Color::new_from_underlying(0.525, 0.5, 0.5);
+        let heatmap = canvas.diff_heatmap(&altered, Float(10.0)).unwrap();
+        assert_eq!(
+            heatmap[Coord2D::new(1, 1)],
+            Color::new_from_underlying(1.0, 0.0, 0.0)
+        );
+        assert_eq!(
+            heatmap[Coord2D::new(2, 0)],
+            Color::new_from_underlying(0.0, 0.5, 0.5)
+        );
+        assert_eq!(heatmap[Coord2D::new(0, 0)], blue);
+        assert!(canvas
+            .diff_heatmap(&Canvas::new(Coord2D::new(1, 1)), Float(1.0))
+            .is_err());
+    }
+
+    #[test]
+    fn warp_affine() {
+        use crate::matrix::transform::{scaling, translation};
+        let mut canvas = Canvas::new(Coord2D::new(3, 2));
+        canvas.iter_mut().for_each(|(coord, color)| {
+            *color = Color::new_from_underlying(
+                coord.x as UnderlyingFloat / 2.0,
+                coord.y as UnderlyingFloat,
+                0.5,
+            )
+        });
+        assert_eq!(
+            canvas.warp_affine(&Matrix4::identity(), canvas.get_dim()),
+            canvas
+        );
+
+        let shifted = canvas.warp_affine(
+            &translation(Float(1.0), Float(0.0), Float(0.0)),
+            Coord2D::new(4, 2),
+        );
+        let black = Color::new_from_underlying(0.0, 0.0, 0.0);
+        shifted.iter().for_each(|(coord, color)| {
+            let expected = match coord.x {
+                0 => black,
+                x => canvas[Coord2D::new(x - 1, coord.y)],
+            };
+            assert_eq!(*color, expected);
+        });
+
+        let half = canvas.warp_affine(
+            &translation(Float(0.5), Float(0.0), Float(0.0)),
+            canvas.get_dim(),
+        );
+        assert_eq!(
+            half[Coord2D::new(1, 1)],
+            Color::new_from_underlying(0.25, 1.0, 0.5)
+        );
+
+        let singular = scaling(Float(0.0), Float(1.0), Float(1.0));
+        assert_eq!(
+            canvas.warp_affine(&singular, canvas.get_dim()),
+            Canvas::new(canvas.get_dim())
+        );
+    }
+
+    #[test]
+    fn tiled() {
+        let mut canvas = Canvas::new(Coord2D::new(2, 2));
+        for index in 0..canvas.size() {
+            let v = index as UnderlyingFloat * 0.25;
+            canvas[index] = Color::new_from_underlying(v, v, v);
+        }
+        let tiled = canvas.tiled(Coord2D::new(5, 5));
+        assert_eq!(tiled.get_dim(), Coord2D::new(5, 5));
+        for (coord, color) in tiled.iter() {
+            assert_eq!(*color, canvas[Coord2D::new(coord.x % 2, coord.y % 2)]);
+        }
+        assert_eq!(tiled[Coord2D::new(2, 0)], canvas[Coord2D::new(0, 0)]);
+        assert_eq!(tiled[Coord2D::new(4, 3)], canvas[Coord2D::new(0, 1)]);
+        let cropped = tiled.tiled(Coord2D::new(1, 2));
+        assert_eq!(cropped[Coord2D::new(0, 1)], canvas[Coord2D::new(0, 1)]);
+        assert_eq!(
+            Canvas::new(Coord2D::new(0, 0)).tiled(Coord2D::new(2, 1)),
+            Canvas::new(Coord2D::new(2, 1))
+        );
+    }
+
+    #[test]
+    fn blit_chroma_keyed() {
+        let green = Color::new_from_underlying(0.0, 1.0, 0.0);
+        let blue = Color::new_from_underlying(0.0, 0.0, 1.0);
+        let red = Color::new_from_underlying(1.0, 0.0, 0.0);
+        let mut sprite = Canvas::new(Coord2D::new(3, 3));
+        sprite
+            .iter_mut()
+            .for_each(|(_, color)| *color = Color::new_from_underlying(0.02, 0.98, 0.0));
+        sprite[Coord2D::new(1, 1)] = red;
+        sprite[Coord2D::new(2, 2)] = red;
+
+        let mut canvas = Canvas::new(Coord2D::new(4, 4));
+        canvas.iter_mut().for_each(|(_, color)| *color = blue);
+        canvas.blit_chroma_keyed(&sprite, Coord2D::new(1, 2), green, Float(0.1));
+        canvas.iter().for_each(|(coord, color)| {
+            let expected = match (coord.x, coord.y) {
+                (2, 3) => red,
+                _ => blue,
+            };
+            assert_eq!(*color, expected);
+        });
+    }
+
+    #[test]
+    fn premultiply_alpha_round_trip() {
+        let dim = Coord2D::new(4, 2);
+        let mut canvas = Canvas::new(dim);
+        canvas
+            .iter_mut()
+            .for_each(|(_, color)| *color = Color::new_from_underlying(0.8, 0.4, 0.2));
+        let original = canvas.clone();
+        let mut alpha = Canvas::new(dim);
+        alpha.iter_mut().for_each(|(coord, a)| {
+            let value = if coord.x < 2 { 0.5 } else { 1.0 };
+            *a = Color::new_from_underlying(value, value, value);
+        });
+
+        assert!(canvas.premultiply_alpha(&alpha).is_ok());
+        canvas.iter().for_each(|(coord, color)| {
+            let expected = if coord.x < 2 {
+                Color::new_from_underlying(0.4, 0.2, 0.1)
+            } else {
+                original[coord]
+            };
+            assert_eq!(*color, expected);
+        });
+
+        assert!(canvas.unpremultiply_alpha(&alpha).is_ok());
+        assert_eq!(canvas, original);
+
+        let mismatched = Canvas::new(Coord2D::new(2, 4));
+        assert!(canvas.premultiply_alpha(&mismatched).is_err());
+        assert!(canvas.unpremultiply_alpha(&mismatched).is_err());
+    }
+
+    #[test]
+    fn write_pam() {
+        let mut canvas = Canvas::new(Coord2D::new(3, 2));
+        canvas[Coord2D::new(1, 0)] = Color::new_from_underlying(1.0, 0.5, 0.0);
+        let mut data: Vec<u8> = Vec::new();
+        assert!(canvas.write_pam(None, &mut data).is_ok());
+        let header = "P7\nWIDTH 3\nHEIGHT 2\nDEPTH 3\nMAXVAL 255\nTUPLTYPE RGB\nENDHDR\n";
+        assert_eq!(&data[..header.len()], header.as_bytes());
+        assert_eq!(data.len(), header.len() + 18);
+        assert_eq!(data[header.len() + 3..header.len() + 6], [255, 128, 0]);
+
+        let mut alpha = Canvas::new(Coord2D::new(3, 2));
+        alpha[Coord2D::new(1, 0)] = Color::new_from_underlying(1.0, 1.0, 1.0);
+        let mut data: Vec<u8> = Vec::new();
+        assert!(canvas.write_pam(Some(&alpha), &mut data).is_ok());
+        let header = "P7\nWIDTH 3\nHEIGHT 2\nDEPTH 4\nMAXVAL 255\nTUPLTYPE RGB_ALPHA\nENDHDR\n";
+        assert_eq!(&data[..header.len()], header.as_bytes());
+        assert_eq!(data.len(), header.len() + 24);
+        assert_eq!(
+            data[header.len()..header.len() + 8],
+            [0, 0, 0, 0, 255, 128, 0, 255]
+        );
+
+        let mismatched = Canvas::new(Coord2D::new(2, 3));
+        let mut data: Vec<u8> = Vec::new();
+        assert!(canvas.write_pam(Some(&mismatched), &mut data).is_err());
+        assert!(data.is_empty());
+    }
+
+    #[test]
+    fn write_ppm_srgb() {
+        let mut canvas = Canvas::new(Coord2D::new(1, 1));
+        canvas[0] = Color::new_from_underlying(0.5, 0.0, 1.0);
+        let mut linear: Vec<u8> = Vec::new();
+        assert!(canvas.write_ppm(&mut linear).is_ok());
+        assert_eq!(
+            std::str::from_utf8(&linear).unwrap(),
+            "P3\n1 1\n255\n128 0 255\n"
+        );
+        let mut srgb: Vec<u8> = Vec::new();
+        assert!(canvas.write_ppm_srgb(&mut srgb).is_ok());
+        assert_eq!(
+            std::str::from_utf8(&srgb).unwrap(),
+            "P3\n1 1\n255\n188 0 255\n"
+        );
+    }
+
+    #[test]
+    fn channel() {
+        let mut canvas = Canvas::new(Coord2D::new(2, 1));
+        canvas[1] = Color::new_from_underlying(0.2, 0.4, 0.6);
+        let green = canvas.channel(1);
+        assert_eq!(green[0], Color::new_from_underlying(0.0, 0.0, 0.0));
+        assert_eq!(green[1], Color::new_from_underlying(0.4, 0.4, 0.4));
+    }
+
+    #[test]
+    fn save_channels() {
+        let dir = std::env::temp_dir().join(format!("rtlib_save_channels_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut canvas = Canvas::new(Coord2D::new(2, 1));
+        canvas[0] = Color::new_from_underlying(1.0, 0.5, 0.0);
+        canvas[1] = Color::new_from_underlying(0.0, 0.2, 1.0);
+        canvas.save_channels(&dir, "out").unwrap();
+        let green = std::fs::read_to_string(dir.join("out_g.ppm")).unwrap();
+        assert_eq!(green, "P3\n2 1\n255\n128 128 128 51 51 51\n");
+        assert!(dir.join("out_r.ppm").exists());
+        assert!(dir.join("out_b.ppm").exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn write_ppm() {
         let canvas = Canvas::new(Coord2D::new(5, 3));
@@ -266,6 +2502,148 @@ mod tests {
         let conv = std::str::from_utf8(&data);
         assert!(conv.is_ok());
         let str = conv.unwrap();
-        assert_eq!(str.chars().rev().next().unwrap(), '\n');
+        assert_eq!(str.chars().next_back().unwrap(), '\n');
+    }
+
+    #[test]
+    fn read_ppm_round_trip() {
+        let mut canvas = Canvas::new(Coord2D::new(3, 2));
+        canvas[0] = Color::new_from_underlying(1.0, 0.2, 0.0);
+        canvas[2] = Color::new_from_underlying(0.6, 0.4, 0.8);
+        canvas[4] = Color::new_from_underlying(0.0, 1.0, 1.0);
+        let mut data: Vec<u8> = Vec::new();
+        canvas.write_ppm(&mut data).unwrap();
+        assert_eq!(Canvas::read_ppm(&mut data.as_slice()).unwrap(), canvas);
+    }
+
+    #[test]
+    fn read_ppm_maxval_and_whitespace() {
+        let mut data: &[u8] = b"P3 1 2 # size\n 15\n15\t0 0\n\n 5 # half\n 10 15";
+        let canvas = Canvas::read_ppm(&mut data).unwrap();
+        assert_eq!(canvas[0], Color::new_from_underlying(1.0, 0.0, 0.0));
+        assert_eq!(
+            canvas[1],
+            Color::new_from_underlying(1.0 / 3.0, 2.0 / 3.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn read_ppm_errors() {
+        let read = |data: &[u8]| Canvas::read_ppm(&mut &data[..]);
+        assert!(matches!(read(b"P6\n1 1\n255\n"), Err(PpmError::BadMagic(m)) if m == "P6"));
+        assert!(matches!(
+            read(b"P3\n1 x\n255\n"),
+            Err(PpmError::BadHeader(_))
+        ));
+        assert!(matches!(read(b"P3\n1 1\n"), Err(PpmError::BadHeader(_))));
+        assert!(matches!(
+            read(b"P3\n1 1\n255\n1 2\n"),
+            Err(PpmError::SampleCount {
+                expected: 3,
+                actual: 2
+            })
+        ));
+        assert!(matches!(
+            read(b"P3\n1 1\n255\n1 2 256\n"),
+            Err(PpmError::SampleOverflow {
+                sample: 256,
+                maxval: 255
+            })
+        ));
+        assert!(matches!(
+            read(b"P3\n1 1\n255\n1 x 3\n"),
+            Err(PpmError::InvalidSample(token)) if token == "x"
+        ));
+        assert!(matches!(
+            read(b"P3\n1 1\n255\n1 2 -3\n"),
+            Err(PpmError::InvalidSample(_))
+        ));
+        assert!(matches!(
+            read(b"P3\n1 1\n255\n1 2 99999999999\n"),
+            Err(PpmError::SampleOverflow {
+                sample: 99999999999,
+                maxval: 255
+            })
+        ));
+        assert!(matches!(
+            read(b"P3\n1 1\n255\n1 2 99999999999999999999999\n"),
+            Err(PpmError::SampleOverflow {
+                sample: u64::MAX,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn read_ppm_oversized_header() {
+        let mut data: &[u8] = b"P3\n4294967295 4294967295\n255\n0 0 0\n";
+        assert!(matches!(
+            Canvas::read_ppm(&mut data),
+            Err(PpmError::BadHeader(_))
+        ));
+        let mut data: &[u8] = b"P3\n100000 100000\n255\n0 0 0\n";
+        assert!(matches!(
+            Canvas::read_ppm(&mut data),
+            Err(PpmError::SampleCount { actual: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn read_binary_ppm_round_trip() {
+        let mut canvas = Canvas::new(Coord2D::new(2, 3));
+        canvas[1] = Color::new_from_underlying(1.0, 0.2, 0.0);
+        canvas[3] = Color::new_from_underlying(0.6, 0.4, 0.8);
+        canvas[5] = Color::new_from_underlying(0.0, 1.0, 1.0);
+        let mut data: Vec<u8> = Vec::new();
+        canvas.write_binary_ppm(&mut data).unwrap();
+        assert_eq!(
+            Canvas::read_binary_ppm(&mut data.as_slice()).unwrap(),
+            canvas
+        );
+    }
+
+    #[test]
+    fn read_binary_ppm_wide_samples() {
+        // the first sample byte is whitespace and must not be swallowed by the header
+        let mut data: &[u8] = b"P6 # wide\n1 1 65535\n\x20\x00\xff\xff\x00\x00";
+        let canvas = Canvas::read_binary_ppm(&mut data).unwrap();
+        assert_eq!(
+            canvas[0],
+            Color::new_from_underlying(8192.0 / 65535.0, 1.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn read_binary_ppm_oversized_header() {
+        let mut data: &[u8] = b"P6\n4294967295 4294967295\n255\n";
+        assert!(matches!(
+            Canvas::read_binary_ppm(&mut data),
+            Err(PpmError::BadHeader(_))
+        ));
+        let mut data: &[u8] = b"P6\n100000 100000\n65535\n\x00\x01\x00\x02";
+        assert!(matches!(
+            Canvas::read_binary_ppm(&mut data),
+            Err(PpmError::SampleCount { actual: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn read_binary_ppm_truncated() {
+        let mut data: &[u8] = b"P6\n2 1\n255\n\x01\x02\x03\x04";
+        assert!(matches!(
+            Canvas::read_binary_ppm(&mut data),
+            Err(PpmError::SampleCount {
+                expected: 6,
+                actual: 4
+            })
+        ));
+        let mut data: &[u8] = b"P6\n1 1\n200\n\x01\xc9\x03";
+        assert!(matches!(
+            Canvas::read_binary_ppm(&mut data),
+            Err(PpmError::SampleOverflow {
+                sample: 201,
+                maxval: 200
+            })
+        ));
     }
 }
@@ -0,0 +1,163 @@
+use crate::prelude::*;
+
+#[derive(Debug)]
+pub enum PngError {
+    TooLarge(Coord2D),
+    Encoding(png::EncodingError),
+}
+
+impl std::fmt::Display for PngError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PngError::TooLarge(dim) => write!(f, "{} exceeds the PNG size limit", dim),
+            PngError::Encoding(err) => write!(f, "png encoding failed: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for PngError {}
+
+impl From<png::EncodingError> for PngError {
+    fn from(err: png::EncodingError) -> Self {
+        PngError::Encoding(err)
+    }
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encodes data as padded standard base64
+fn base64_encode(data: &[u8]) -> String {
+    let mut res = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let bits = (bytes[0] as u32) << 16 | (bytes[1] as u32) << 8 | bytes[2] as u32;
+        for index in 0..4 {
+            if index <= chunk.len() {
+                res.push(BASE64_ALPHABET[(bits >> (18 - 6 * index) & 0x3f) as usize] as char);
+            } else {
+                res.push('=');
+            }
+        }
+    }
+    res
+}
+
+impl Canvas {
+    /// Writes the canvas as an 8 bit RGB PNG
+    pub fn write_png(&self, w: &mut impl std::io::Write) -> Result<(), PngError> {
+        let (width, height) = match (u32::try_from(self.dim.x), u32::try_from(self.dim.y)) {
+            (Ok(width), Ok(height)) => (width, height),
+            _ => return Err(PngError::TooLarge(self.dim)),
+        };
+        let mut encoder = png::Encoder::new(w, width, height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        let data: Vec<u8> = self
+            .data
+            .iter()
+            .flat_map(|color| {
+                [
+                    Self::u8_representation(color.get_r()),
+                    Self::u8_representation(color.get_g()),
+                    Self::u8_representation(color.get_b()),
+                ]
+            })
+            .collect();
+        writer.write_image_data(&data)?;
+        writer.finish()?;
+        Ok(())
+    }
+
+    /// Encodes the canvas as PNG wrapped in a data:image/png;base64 URL for embedding in HTML
+    pub fn to_png_data_url(&self) -> Result<String, PngError> {
+        let mut data: Vec<u8> = Vec::new();
+        self.write_png(&mut data)?;
+        Ok(format!("data:image/png;base64,{}", base64_encode(&data)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base64_decode(s: &str) -> Vec<u8> {
+        let values: Vec<u32> = s
+            .bytes()
+            .filter(|byte| *byte != b'=')
+            .map(|byte| BASE64_ALPHABET.iter().position(|c| *c == byte).unwrap() as u32)
+            .collect();
+        let mut res = Vec::new();
+        for chunk in values.chunks(4) {
+            let bits = chunk
+                .iter()
+                .enumerate()
+                .fold(0, |bits, (index, value)| bits | value << (18 - 6 * index));
+            res.extend_from_slice(&bits.to_be_bytes()[1..chunk.len()]);
+        }
+        res
+    }
+
+    #[test]
+    fn base64_encode() {
+        assert_eq!(super::base64_encode(b""), "");
+        assert_eq!(super::base64_encode(b"f"), "Zg==");
+        assert_eq!(super::base64_encode(b"fo"), "Zm8=");
+        assert_eq!(super::base64_encode(b"foo"), "Zm9v");
+        assert_eq!(super::base64_encode(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64_decode("Zm9vYmE="), b"fooba");
+    }
+
+    #[test]
+    fn png_error() {
+        let err = PngError::from(png::EncodingError::LimitsExceeded);
+        assert!(matches!(err, PngError::Encoding(_)));
+        assert!(err.to_string().starts_with("png encoding failed"));
+        assert_eq!(
+            PngError::TooLarge(Coord2D::new(1, 2)).to_string(),
+            format!("{} exceeds the PNG size limit", Coord2D::new(1, 2))
+        );
+    }
+
+    #[test]
+    fn to_png_data_url() {
+        let mut canvas = Canvas::new(Coord2D::new(2, 2));
+        canvas[Coord2D::new(1, 0)] = Color::new_from_underlying(0.0, 0.0, 1.0);
+        let url = canvas.to_png_data_url().unwrap();
+        let encoded = url.strip_prefix("data:image/png;base64,").unwrap();
+        let mut expected: Vec<u8> = Vec::new();
+        canvas.write_png(&mut expected).unwrap();
+        let decoded = base64_decode(encoded);
+        assert_eq!(decoded, expected);
+        let reader = png::Decoder::new(std::io::Cursor::new(decoded))
+            .read_info()
+            .unwrap();
+        assert_eq!((reader.info().width, reader.info().height), (2, 2));
+    }
+
+    #[test]
+    fn write_png() {
+        let mut canvas = Canvas::new(Coord2D::new(3, 2));
+        canvas[Coord2D::new(0, 0)] = Color::new_from_underlying(1.0, 0.0, 0.0);
+        canvas[Coord2D::new(2, 1)] = Color::new_from_underlying(0.0, 1.5, 0.2);
+
+        let mut data: Vec<u8> = Vec::new();
+        assert!(canvas.write_png(&mut data).is_ok());
+        assert_eq!(&data[..8], b"\x89PNG\r\n\x1a\n");
+
+        let mut reader = png::Decoder::new(std::io::Cursor::new(data))
+            .read_info()
+            .unwrap();
+        let mut buf = vec![0; reader.output_buffer_size().unwrap()];
+        let info = reader.next_frame(&mut buf).unwrap();
+        assert_eq!((info.width, info.height), (3, 2));
+        assert_eq!(info.color_type, png::ColorType::Rgb);
+        assert_eq!(buf[..3], [255, 0, 0]);
+        assert_eq!(buf[15..18], [0, 255, 51]);
+    }
+}
@@ -0,0 +1,81 @@
+use crate::prelude::*;
+use std::ops::{Deref, DerefMut};
+
+/// Canvas rendered at a multiple of its target resolution for antialiasing
+///
+/// Derefs to the internal high resolution canvas, so all drawing methods work on it directly
+/// using high resolution coordinates.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Supersampled {
+    canvas: Canvas,
+    factor: usize,
+}
+
+impl Supersampled {
+    pub fn new(dim: Coord2D, factor: usize) -> Self {
+        assert!(factor > 0, "supersampling factor must be positive");
+        Self {
+            canvas: Canvas::new(Coord2D::new(dim.x * factor, dim.y * factor)),
+            factor,
+        }
+    }
+
+    pub fn get_factor(&self) -> usize {
+        self.factor
+    }
+
+    /// Box-downsamples the high resolution canvas to the target resolution
+    ///
+    /// ```
+    /// use rtlib::canvas::Canvas;
+    /// use rtlib::canvas::coord_2d::Coord2D;
+    /// use rtlib::color::Color;
+    /// let mut canvas = Canvas::with_supersampling(Coord2D::new(1, 1), 2);
+    /// canvas.plot(Coord2D::new(0, 0), Color::new_from_underlying(1.0, 1.0, 1.0));
+    /// let resolved = canvas.resolve();
+    /// assert_eq!(resolved.get_dim(), Coord2D::new(1, 1));
+    /// assert_eq!(resolved[0], Color::new_from_underlying(0.25, 0.25, 0.25));
+    /// ```
+    pub fn resolve(self) -> Canvas {
+        let dim = self.canvas.get_dim();
+        self.canvas.downscale_linear(
+            Coord2D::new(dim.x / self.factor, dim.y / self.factor),
+            Float(1.0),
+        )
+    }
+}
+
+impl Deref for Supersampled {
+    type Target = Canvas;
+    fn deref(&self) -> &Self::Target {
+        &self.canvas
+    }
+}
+
+impl DerefMut for Supersampled {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.canvas
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diagonal_line_is_antialiased() {
+        let white = Color::new_from_underlying(1.0, 1.0, 1.0);
+        let mut canvas = Canvas::with_supersampling(Coord2D::new(4, 4), 2);
+        assert_eq!(canvas.get_dim(), Coord2D::new(8, 8));
+        (0..8).for_each(|i| canvas.plot(Coord2D::new(i, i), white));
+        let resolved = canvas.resolve();
+        assert_eq!(resolved.get_dim(), Coord2D::new(4, 4));
+        resolved.iter().for_each(|(coord, color)| {
+            let expected = if coord.x == coord.y { 0.5 } else { 0.0 };
+            assert_eq!(
+                *color,
+                Color::new_from_underlying(expected, expected, expected)
+            );
+        });
+    }
+}
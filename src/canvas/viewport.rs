@@ -0,0 +1,197 @@
+use crate::prelude::*;
+
+/// Maps a rectangle of the world xy plane onto the pixels of a canvas
+///
+/// world_min lands on the bottom left and world_max on the top right pixel, so y grows
+/// upwards in the world and downwards on the canvas. The z and w components are ignored.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Viewport {
+    pub world_min: Tuple,
+    pub world_max: Tuple,
+    pub dim: Coord2D,
+}
+
+impl Viewport {
+    pub fn new(world_min: Tuple, world_max: Tuple, dim: Coord2D) -> Self {
+        Self {
+            world_min,
+            world_max,
+            dim,
+        }
+    }
+
+    /// Creates a viewport with square pixels that contains the given world rectangle
+    ///
+    /// The shorter side of the rectangle is widened symmetrically to match the aspect ratio of
+    /// dim.
+    ///
+    /// ```
+    /// use rtlib::canvas::coord_2d::Coord2D;
+    /// use rtlib::canvas::viewport::Viewport;
+    /// use rtlib::tuple::Tuple;
+    /// let viewport = Viewport::fitted(
+    ///     Tuple::point_from_underlying(0.0, 0.0, 0.0),
+    ///     Tuple::point_from_underlying(2.0, 2.0, 0.0),
+    ///     Coord2D::new(5, 3),
+    /// );
+    /// assert_eq!(viewport.world_min, Tuple::point_from_underlying(-1.0, 0.0, 0.0));
+    /// assert_eq!(viewport.world_max, Tuple::point_from_underlying(3.0, 2.0, 0.0));
+    /// ```
+    pub fn fitted(world_min: Tuple, world_max: Tuple, dim: Coord2D) -> Self {
+        let mut res = Self::new(world_min, world_max, dim);
+        let (x_steps, y_steps) = res.steps();
+        let width = world_max.get_x() - world_min.get_x();
+        let height = world_max.get_y() - world_min.get_y();
+        let pixel = (width / x_steps).max(height / y_steps);
+        let grow_x = (pixel * x_steps - width) / 2.0;
+        let grow_y = (pixel * y_steps - height) / 2.0;
+        *res.world_min.get_mut_x() -= grow_x;
+        *res.world_max.get_mut_x() += grow_x;
+        *res.world_min.get_mut_y() -= grow_y;
+        *res.world_max.get_mut_y() += grow_y;
+        res
+    }
+
+    fn steps(&self) -> (Float, Float) {
+        let steps = |len: usize| Float(len.saturating_sub(1).max(1) as UnderlyingFloat);
+        (steps(self.dim.x), steps(self.dim.y))
+    }
+
+    /// Maps a world point to the nearest pixel, or None if it lies outside the viewport
+    ///
+    /// NaN coordinates and axes of zero world extent map to None.
+    ///
+    /// ```
+    /// use rtlib::canvas::coord_2d::Coord2D;
+    /// use rtlib::canvas::viewport::Viewport;
+    /// use rtlib::tuple::Tuple;
+    /// let viewport = Viewport::new(
+    ///     Tuple::point_from_underlying(-1.0, -1.0, 0.0),
+    ///     Tuple::point_from_underlying(1.0, 1.0, 0.0),
+    ///     Coord2D::new(3, 3),
+    /// );
+    /// assert_eq!(
+    ///     viewport.world_to_pixel(Tuple::point_from_underlying(0.9, 0.1, 0.0)),
+    ///     Some(Coord2D::new(2, 1))
+    /// );
+    /// assert_eq!(viewport.world_to_pixel(Tuple::point_from_underlying(1.5, 0.0, 0.0)), None);
+    /// ```
+    pub fn world_to_pixel(&self, p: Tuple) -> Option<Coord2D> {
+        let (x_steps, y_steps) = self.steps();
+        let x = ((p.get_x() - self.world_min.get_x())
+            / (self.world_max.get_x() - self.world_min.get_x())
+            * x_steps)
+            .round();
+        let y = ((self.world_max.get_y() - p.get_y())
+            / (self.world_max.get_y() - self.world_min.get_y())
+            * y_steps)
+            .round();
+        if !x.0.is_finite()
+            || !y.0.is_finite()
+            || x.0 < 0.0
+            || y.0 < 0.0
+            || x.0 >= self.dim.x as UnderlyingFloat
+            || y.0 >= self.dim.y as UnderlyingFloat
+        {
+            return None;
+        }
+        Some(Coord2D::new(x.0 as usize, y.0 as usize))
+    }
+
+    /// Maps a pixel back to the world point it represents
+    pub fn pixel_to_world(&self, c: Coord2D) -> Tuple {
+        let (x_steps, y_steps) = self.steps();
+        let x = self.world_min.get_x()
+            + (self.world_max.get_x() - self.world_min.get_x())
+                * (Float(c.x as UnderlyingFloat) / x_steps);
+        let y = self.world_max.get_y()
+            - (self.world_max.get_y() - self.world_min.get_y())
+                * (Float(c.y as UnderlyingFloat) / y_steps);
+        Tuple::point(x, y, Float(0.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn corners_round_trip() {
+        let world_min = Tuple::point_from_underlying(-2.0, 1.0, 0.0);
+        let world_max = Tuple::point_from_underlying(6.0, 5.0, 0.0);
+        let viewport = Viewport::new(world_min, world_max, Coord2D::new(9, 5));
+        let corners = [
+            (
+                Tuple::point_from_underlying(-2.0, 5.0, 0.0),
+                Coord2D::new(0, 0),
+            ),
+            (
+                Tuple::point_from_underlying(6.0, 5.0, 0.0),
+                Coord2D::new(8, 0),
+            ),
+            (
+                Tuple::point_from_underlying(-2.0, 1.0, 0.0),
+                Coord2D::new(0, 4),
+            ),
+            (
+                Tuple::point_from_underlying(6.0, 1.0, 0.0),
+                Coord2D::new(8, 4),
+            ),
+        ];
+        for (world, pixel) in corners {
+            assert_eq!(viewport.world_to_pixel(world), Some(pixel));
+            assert_eq!(viewport.pixel_to_world(pixel), world);
+        }
+        assert_eq!(
+            viewport.world_to_pixel(Tuple::point_from_underlying(2.0, 3.0, 0.0)),
+            Some(Coord2D::new(4, 2))
+        );
+        assert_eq!(
+            viewport.world_to_pixel(Tuple::point_from_underlying(2.0, 0.0, 0.0)),
+            None
+        );
+    }
+
+    #[test]
+    fn world_to_pixel_rejects_nan() {
+        let viewport = Viewport::new(
+            Tuple::point_from_underlying(-1.0, -1.0, 0.0),
+            Tuple::point_from_underlying(1.0, 1.0, 0.0),
+            Coord2D::new(3, 3),
+        );
+        assert_eq!(
+            viewport.world_to_pixel(Tuple::point_from_underlying(f64::NAN, 0.0, 0.0)),
+            None
+        );
+        let flat = Viewport::new(
+            Tuple::point_from_underlying(0.0, -1.0, 0.0),
+            Tuple::point_from_underlying(0.0, 1.0, 0.0),
+            Coord2D::new(3, 3),
+        );
+        assert_eq!(
+            flat.world_to_pixel(Tuple::point_from_underlying(0.0, 0.0, 0.0)),
+            None
+        );
+    }
+
+    #[test]
+    fn fitted_has_square_pixels() {
+        let viewport = Viewport::fitted(
+            Tuple::point_from_underlying(0.0, 0.0, 0.0),
+            Tuple::point_from_underlying(4.0, 1.0, 0.0),
+            Coord2D::new(3, 3),
+        );
+        assert_eq!(
+            viewport.world_min,
+            Tuple::point_from_underlying(0.0, -1.5, 0.0)
+        );
+        assert_eq!(
+            viewport.world_max,
+            Tuple::point_from_underlying(4.0, 2.5, 0.0)
+        );
+        let origin = viewport.pixel_to_world(Coord2D::new(0, 0));
+        let right = viewport.pixel_to_world(Coord2D::new(1, 0));
+        let down = viewport.pixel_to_world(Coord2D::new(0, 1));
+        assert_eq!(origin.distance(right), origin.distance(down));
+    }
+}
@@ -45,6 +45,163 @@ impl Color {
         Self::new(Float(r), Float(g), Float(b))
     }
 
+    /// Constructs a Color from hue in degrees, saturation and value
+    ///
+    /// ```
+    /// use rtlib::color::Color;
+    /// use rtlib::float::Float;
+    /// assert_eq!(Color::from_hsv(Float(0.0), Float(1.0), Float(1.0)), Color::new_from_underlying(1.0, 0.0, 0.0));
+    /// assert_eq!(Color::from_hsv(Float(240.0), Float(0.5), Float(1.0)), Color::new_from_underlying(0.5, 0.5, 1.0));
+    /// assert_eq!(Color::from_hsv(Float(-60.0), Float(1.0), Float(0.5)), Color::new_from_underlying(0.5, 0.0, 0.5));
+    /// ```
+    pub fn from_hsv(h: Float, s: Float, v: Float) -> Self {
+        let h = h.0.rem_euclid(360.0) / 60.0;
+        let c = v.0 * s.0;
+        let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+        let m = v.0 - c;
+        let (r, g, b) = match h as u32 {
+            0 => (c, x, 0.0),
+            1 => (x, c, 0.0),
+            2 => (0.0, c, x),
+            3 => (0.0, x, c),
+            4 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+        Self::new_from_underlying(r + m, g + m, b + m)
+    }
+
+    /// Approximates the color of a blackbody radiator at temp Kelvin
+    ///
+    /// Uses Tanner Helland's piecewise fit, valid from 1000K to 40000K; temperatures outside
+    /// are clamped to that range.
+    ///
+    /// ```
+    /// use rtlib::color::Color;
+    /// use rtlib::float::Float;
+    /// let daylight = Color::from_kelvin(Float(6500.0));
+    /// assert!(daylight.elements.iter().all(|c| *c > 0.95));
+    /// let candle = Color::from_kelvin(Float(2000.0));
+    /// assert_eq!(candle.get_r(), 1.0);
+    /// assert!(candle.get_g() > 0.5 && candle.get_g() < 0.6);
+    /// assert!(candle.get_b() < 0.1);
+    /// ```
+    pub fn from_kelvin(temp: Float) -> Self {
+        let t = temp.0.clamp(1000.0, 40000.0) / 100.0;
+        let r = if t <= 66.0 {
+            255.0
+        } else {
+            329.698727446 * (t - 60.0).powf(-0.1332047592)
+        };
+        let g = if t <= 66.0 {
+            99.4708025861 * t.ln() - 161.1195681661
+        } else {
+            288.1221695283 * (t - 60.0).powf(-0.0755148492)
+        };
+        let b = if t >= 66.0 {
+            255.0
+        } else if t <= 19.0 {
+            0.0
+        } else {
+            138.5177312231 * (t - 10.0).ln() - 305.0447927307
+        };
+        let normalize = |c: UnderlyingFloat| c.clamp(0.0, 255.0) / 255.0;
+        Self::new_from_underlying(normalize(r), normalize(g), normalize(b))
+    }
+
+    /// Multiplies the channels by alpha
+    ///
+    /// ```
+    /// use rtlib::color::Color;
+    /// use rtlib::float::Float;
+    /// let c = Color::new_from_underlying(0.2, 0.4, 1.0);
+    /// assert_eq!(c.premultiply(Float(0.5)), Color::new_from_underlying(0.1, 0.2, 0.5));
+    /// ```
+    pub fn premultiply(self, alpha: Float) -> Self {
+        self * alpha
+    }
+
+    /// Divides the channels by alpha, yielding black for zero alpha
+    ///
+    /// ```
+    /// use rtlib::color::Color;
+    /// use rtlib::float::Float;
+    /// let c = Color::new_from_underlying(0.1, 0.2, 0.5);
+    /// assert_eq!(c.unpremultiply(Float(0.5)), Color::new_from_underlying(0.2, 0.4, 1.0));
+    /// assert_eq!(c.unpremultiply(Float(0.0)), Color::new_from_underlying(0.0, 0.0, 0.0));
+    /// ```
+    pub fn unpremultiply(self, alpha: Float) -> Self {
+        if alpha == 0.0 {
+            return Self::new_from_underlying(0.0, 0.0, 0.0);
+        }
+        self * (Float(1.0) / alpha)
+    }
+
+    /// Euclidean distance between two Colors in RGB space
+    ///
+    /// ```
+    /// use rtlib::color::Color;
+    /// let a = Color::new_from_underlying(0.0, 0.0, 0.0);
+    /// let b = Color::new_from_underlying(0.0, 0.3, 0.4);
+    /// assert_eq!(a.distance(b), 0.5);
+    /// ```
+    pub fn distance(&self, other: Color) -> Float {
+        let diff = *self - other;
+        diff.elements.iter().map(|f| *f * *f).sum::<Float>().sqrt()
+    }
+
+    /// Relative luminance using the Rec. 709 weights
+    ///
+    /// ```
+    /// use rtlib::color::Color;
+    /// assert_eq!(Color::new_from_underlying(1.0, 1.0, 1.0).luminance(), 1.0);
+    /// assert_eq!(Color::new_from_underlying(0.0, 1.0, 0.0).luminance(), 0.7152);
+    /// ```
+    pub fn luminance(&self) -> Float {
+        Float(0.2126) * self.get_r() + Float(0.7152) * self.get_g() + Float(0.0722) * self.get_b()
+    }
+
+    /// Linearly interpolates from self (t = 0) to other (t = 1)
+    ///
+    /// ```
+    /// use rtlib::color::Color;
+    /// use rtlib::float::Float;
+    /// let a = Color::new_from_underlying(0.0, 0.5, 1.0);
+    /// let b = Color::new_from_underlying(1.0, 0.5, 0.0);
+    /// assert_eq!(a.lerp(b, Float(0.25)), Color::new_from_underlying(0.25, 0.5, 0.75));
+    /// ```
+    pub fn lerp(self, other: Color, t: Float) -> Self {
+        self + (other - self) * t
+    }
+
+    /// Mixes like light: the channels are added and clamped to [0, 1]
+    ///
+    /// ```
+    /// use rtlib::color::Color;
+    /// let red = Color::new_from_underlying(1.0, 0.0, 0.0);
+    /// let green = Color::new_from_underlying(0.0, 1.0, 0.0);
+    /// assert_eq!(red.mix_additive(green), Color::new_from_underlying(1.0, 1.0, 0.0));
+    /// assert_eq!(red.mix_additive(red), red);
+    /// ```
+    pub fn mix_additive(self, other: Color) -> Self {
+        let mut res = self + other;
+        res.elements.iter_mut().for_each(Float::saturate_assign);
+        res
+    }
+
+    /// Mixes like pigment: the channels are multiplied and clamped to [0, 1]
+    ///
+    /// ```
+    /// use rtlib::color::Color;
+    /// let cyan = Color::new_from_underlying(0.0, 1.0, 1.0);
+    /// let yellow = Color::new_from_underlying(1.0, 1.0, 0.0);
+    /// assert_eq!(cyan.mix_subtractive(yellow), Color::new_from_underlying(0.0, 1.0, 0.0));
+    /// ```
+    pub fn mix_subtractive(self, other: Color) -> Self {
+        let mut res = self * other;
+        res.elements.iter_mut().for_each(Float::saturate_assign);
+        res
+    }
+
     pub fn get_r(&self) -> Float {
         self.elements[0]
     }
@@ -8,6 +8,521 @@ impl Float {
     pub fn sqrt(self) -> Self {
         Self(self.0.sqrt())
     }
+
+    pub fn abs(self) -> Self {
+        Self(self.0.abs())
+    }
+
+    /// Returns 1 for positive and -1 for negative values
+    ///
+    /// Like f64, signed zeros are not mapped to zero: 0.0 yields 1.0 and -0.0 yields -1.0.
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// assert_eq!(Float(-3.0).signum(), Float(-1.0));
+    /// assert_eq!(Float(0.0).signum(), Float(1.0));
+    /// ```
+    pub fn signum(self) -> Self {
+        Self(self.0.signum())
+    }
+
+    /// Returns the magnitude of self with the sign of sign
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// assert_eq!(Float(3.0).copysign(Float(-0.5)), Float(-3.0));
+    /// ```
+    pub fn copysign(self, sign: Float) -> Self {
+        Self(self.0.copysign(sign.0))
+    }
+
+    /// Rounds towards negative infinity
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// assert_eq!(Float(2.7).floor(), 2.0);
+    /// assert_eq!(Float(-2.3).floor(), -3.0);
+    /// ```
+    pub fn floor(self) -> Self {
+        Self(self.0.floor())
+    }
+
+    /// Rounds towards positive infinity
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// assert_eq!(Float(2.3).ceil(), 3.0);
+    /// assert_eq!(Float(-2.3).ceil(), -2.0);
+    /// ```
+    pub fn ceil(self) -> Self {
+        Self(self.0.ceil())
+    }
+
+    /// Rounds half-way cases away from zero
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// assert_eq!(Float(2.5).round(), 3.0);
+    /// assert_eq!(Float(-2.5).round(), -3.0);
+    /// ```
+    pub fn round(self) -> Self {
+        Self(self.0.round())
+    }
+
+    /// Rounds towards zero
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// assert_eq!(Float(2.7).trunc(), 2.0);
+    /// assert_eq!(Float(-2.7).trunc(), -2.0);
+    /// ```
+    pub fn trunc(self) -> Self {
+        Self(self.0.trunc())
+    }
+
+    /// Returns the fractional part, carrying the sign of self
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// assert_eq!(Float(2.75).fract(), 0.75);
+    /// assert_eq!(Float(-2.75).fract(), -0.75);
+    /// ```
+    pub fn fract(self) -> Self {
+        Self(self.0.fract())
+    }
+
+    pub fn sin(self) -> Self {
+        Self(self.0.sin())
+    }
+
+    pub fn cos(self) -> Self {
+        Self(self.0.cos())
+    }
+
+    pub fn tan(self) -> Self {
+        Self(self.0.tan())
+    }
+
+    pub fn asin(self) -> Self {
+        Self(self.0.asin())
+    }
+
+    pub fn acos(self) -> Self {
+        Self(self.0.acos())
+    }
+
+    pub fn atan(self) -> Self {
+        Self(self.0.atan())
+    }
+
+    pub fn exp(self) -> Self {
+        Self(self.0.exp())
+    }
+
+    pub fn ln(self) -> Self {
+        Self(self.0.ln())
+    }
+
+    /// Four quadrant arctangent of self (y) and x in the range (-π, π]
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// assert_eq!(Float(1.0).atan2(Float(1.0)), std::f64::consts::FRAC_PI_4);
+    /// assert_eq!(Float(0.0).atan2(Float(-1.0)), std::f64::consts::PI);
+    /// ```
+    pub fn atan2(self, x: Float) -> Self {
+        Self(self.0.atan2(x.0))
+    }
+
+    /// Length of the hypotenuse of a right triangle with legs self and other
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// assert_eq!(Float(1.0).hypot(Float(1.0)), 2.0f64.sqrt());
+    /// ```
+    pub fn hypot(self, other: Float) -> Self {
+        Self(self.0.hypot(other.0))
+    }
+
+    /// Linearly interpolates from self to other, returning the exact endpoints for t outside
+    /// (0, 1)
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// let (a, b) = (Float(0.1), Float(0.7));
+    /// assert!(Float::raw_eq(a.lerp_snapped(b, Float(-0.1)), a));
+    /// assert_eq!(a.lerp_snapped(b, Float(0.5)), 0.4);
+    /// assert!(Float::raw_eq(a.lerp_snapped(b, Float(1.1)), b));
+    /// ```
+    pub fn lerp_snapped(self, other: Float, t: Float) -> Self {
+        if t.0 <= 0.0 {
+            self
+        } else if t.0 >= 1.0 {
+            other
+        } else {
+            self + (other - self) * t
+        }
+    }
+
+    /// Encodes a linear value with the piecewise sRGB transfer function
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// assert_eq!(Float(0.002).srgb_encode(), 0.02584);
+    /// assert_eq!(Float(0.5).srgb_encode(), 0.73536);
+    /// assert_eq!(Float(0.5).srgb_encode().srgb_decode(), 0.5);
+    /// assert_eq!(Float(1.0).srgb_encode(), 1.0);
+    /// ```
+    pub fn srgb_encode(self) -> Self {
+        if self.0 <= 0.0031308 {
+            Self(12.92 * self.0)
+        } else {
+            Self(1.055 * self.0.powf(1.0 / 2.4) - 0.055)
+        }
+    }
+
+    /// Decodes an sRGB encoded value to linear with the piecewise sRGB transfer function
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// assert_eq!(Float(0.02584).srgb_decode(), 0.002);
+    /// assert_eq!(Float(0.25).srgb_decode().srgb_encode(), 0.25);
+    /// assert_eq!(Float(0.0).srgb_decode(), 0.0);
+    /// ```
+    pub fn srgb_decode(self) -> Self {
+        if self.0 <= 0.04045 {
+            Self(self.0 / 12.92)
+        } else {
+            Self(((self.0 + 0.055) / 1.055).powf(2.4))
+        }
+    }
+
+    /// Hermite interpolation from 0 at edge0 to 1 at edge1, clamped outside
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// assert_eq!(Float(-1.0).smoothstep(Float(0.0), Float(2.0)), 0.0);
+    /// assert_eq!(Float(0.5).smoothstep(Float(0.0), Float(2.0)), 0.15625);
+    /// assert_eq!(Float(1.0).smoothstep(Float(0.0), Float(2.0)), 0.5);
+    /// assert_eq!(Float(3.0).smoothstep(Float(0.0), Float(2.0)), 1.0);
+    /// ```
+    pub fn smoothstep(self, edge0: Float, edge1: Float) -> Self {
+        let t = ((self - edge0) / (edge1 - edge0)).0.clamp(0.0, 1.0);
+        Self(t * t * (3.0 - 2.0 * t))
+    }
+
+    /// Linearly maps self from [in_min, in_max] to [out_min, out_max]
+    ///
+    /// A zero width input range maps to out_min.
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// assert_eq!(Float(5.0).remap(Float(0.0), Float(10.0), Float(0.0), Float(1.0)), 0.5);
+    /// assert_eq!(Float(15.0).remap(Float(0.0), Float(10.0), Float(0.0), Float(1.0)), 1.5);
+    /// assert_eq!(Float(3.0).remap(Float(2.0), Float(2.0), Float(4.0), Float(8.0)), 4.0);
+    /// ```
+    pub fn remap(self, in_min: Float, in_max: Float, out_min: Float, out_max: Float) -> Self {
+        if in_max == in_min {
+            return out_min;
+        }
+        out_min + (self - in_min) / (in_max - in_min) * (out_max - out_min)
+    }
+
+    /// Like remap, but clamps the result to the output range
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// assert_eq!(Float(15.0).remap_clamped(Float(0.0), Float(10.0), Float(0.0), Float(1.0)), 1.0);
+    /// assert_eq!(Float(-5.0).remap_clamped(Float(0.0), Float(10.0), Float(1.0), Float(0.0)), 1.0);
+    /// ```
+    pub fn remap_clamped(
+        self,
+        in_min: Float,
+        in_max: Float,
+        out_min: Float,
+        out_max: Float,
+    ) -> Self {
+        self.remap(in_min, in_max, out_min, out_max)
+            .clamp(out_min.min(out_max), out_min.max(out_max))
+    }
+
+    /// Offsets self by EPSILON
+    ///
+    /// Intended for nudging ray intersection distances or points off a surface to avoid
+    /// self-shadowing ("shadow acne").
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// use rtlib::prelude::EPSILON;
+    /// assert!(Float::raw_eq(Float(1.0).bias(), Float(1.0 + EPSILON)));
+    /// assert!(Float::raw_eq(Float(1.0).bias_by(Float(-2.0)), Float(1.0 - 2.0 * EPSILON)));
+    /// ```
+    pub fn bias(self) -> Self {
+        self.bias_by(Float(1.0))
+    }
+
+    /// Offsets self by n times EPSILON
+    pub fn bias_by(self, n: Float) -> Self {
+        Self(self.0 + n.0 * EPSILON)
+    }
+
+    /// Clamps self to [lo, hi]
+    ///
+    /// A NaN self stays NaN. The bounds are compared exactly, and lo must not exceed hi
+    /// (checked in debug builds).
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// assert_eq!(Float(1.5).clamp(Float(0.0), Float(1.0)), 1.0);
+    /// assert!(Float(f64::NAN).clamp(Float(0.0), Float(1.0)).0.is_nan());
+    /// ```
+    pub fn clamp(self, lo: Float, hi: Float) -> Self {
+        debug_assert!(lo.0 <= hi.0, "clamp requires lo <= hi");
+        if self.0 < lo.0 {
+            lo
+        } else if self.0 > hi.0 {
+            hi
+        } else {
+            self
+        }
+    }
+
+    /// Returns the smaller of self and other, ignoring a NaN operand
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// assert_eq!(Float(1.0).min(Float(2.0)), 1.0);
+    /// assert_eq!(Float(f64::NAN).min(Float(2.0)), 2.0);
+    /// ```
+    pub fn min(self, other: Float) -> Self {
+        Self(self.0.min(other.0))
+    }
+
+    /// Returns the larger of self and other, ignoring a NaN operand
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// assert_eq!(Float(1.0).max(Float(2.0)), 2.0);
+    /// assert_eq!(Float(f64::NAN).max(Float(2.0)), 2.0);
+    /// ```
+    pub fn max(self, other: Float) -> Self {
+        Self(self.0.max(other.0))
+    }
+
+    /// Clamps self in place to [lo, hi]
+    pub fn clamp_assign(&mut self, lo: Float, hi: Float) {
+        *self = self.clamp(lo, hi);
+    }
+
+    /// Clamps self in place to [0, 1]
+    pub fn saturate_assign(&mut self) {
+        self.clamp_assign(Float(0.0), Float(1.0));
+    }
+
+    /// Rounds to the nearest i32, rejecting NaN, infinities and out of range values
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// assert_eq!(Float(2.6).to_i32(), Some(3));
+    /// assert_eq!(Float(-2.6).to_i32(), Some(-3));
+    /// assert_eq!(Float(f64::NAN).to_i32(), None);
+    /// assert_eq!(Float(1e10).to_i32(), None);
+    /// ```
+    pub fn to_i32(self) -> Option<i32> {
+        let rounded = self.0.round();
+        if rounded.is_finite() && rounded >= i32::MIN as f64 && rounded <= i32::MAX as f64 {
+            Some(rounded as i32)
+        } else {
+            None
+        }
+    }
+
+    /// Rounds to the nearest usize, rejecting NaN, infinities, negatives and out of range values
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// assert_eq!(Float(2.4).to_usize(), Some(2));
+    /// assert_eq!(Float(-1.0).to_usize(), None);
+    /// assert_eq!(Float(f64::NAN).to_usize(), None);
+    /// assert_eq!(Float(f64::INFINITY).to_usize(), None);
+    /// ```
+    pub fn to_usize(self) -> Option<usize> {
+        let rounded = self.0.round();
+        if rounded.is_finite() && rounded >= 0.0 && rounded < usize::MAX as f64 {
+            Some(rounded as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Checks whether self and other differ by less than tolerance
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// assert!(Float(1000.0).approx_eq(Float(1000.4), 0.5));
+    /// assert!(!Float(1000.0).approx_eq(Float(1000.6), 0.5));
+    /// ```
+    pub fn approx_eq(self, other: Float, tolerance: UnderlyingFloat) -> bool {
+        (self.0 - other.0).abs() < tolerance
+    }
+
+    /// Checks whether self and other differ by at most tolerance times the larger magnitude
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// assert!(Float(1e12).approx_eq_relative(Float(1e12 + 1.0), 1e-9));
+    /// assert!(!Float(1e-12).approx_eq_relative(Float(2e-12), 1e-9));
+    /// ```
+    pub fn approx_eq_relative(self, other: Float, tolerance: UnderlyingFloat) -> bool {
+        (self.0 - other.0).abs() <= tolerance * self.0.abs().max(other.0.abs())
+    }
+
+    /// Checks whether self and other are within the larger of an absolute and a relative tolerance
+    ///
+    /// The absolute tolerance governs values near zero, the relative one large magnitudes.
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// assert!(Float(1e-12).close_enough(Float(2e-12), 1e-9, 1e-9));
+    /// assert!(Float(1e12).close_enough(Float(1e12 + 1.0), 1e-9, 1e-9));
+    /// assert!(!Float(1.0).close_enough(Float(1.1), 1e-9, 1e-9));
+    /// ```
+    pub fn close_enough(
+        self,
+        other: Float,
+        abs_tol: UnderlyingFloat,
+        rel_tol: UnderlyingFloat,
+    ) -> bool {
+        let largest = self.0.abs().max(other.0.abs());
+        (self.0 - other.0).abs() <= abs_tol.max(rel_tol * largest)
+    }
+
+    /// Formats like Display, but with -0 shown as 0 and non-finite values as nan, inf or -inf
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// assert_eq!(Float(-0.0).display_canonical(), "0");
+    /// assert_eq!(Float(f64::NEG_INFINITY).display_canonical(), "-inf");
+    /// assert_eq!(Float(1.25).display_canonical(), "1.25");
+    /// ```
+    pub fn display_canonical(&self) -> String {
+        if self.0.is_nan() {
+            "nan".to_string()
+        } else if self.0.is_infinite() {
+            if self.0 > 0.0 { "inf" } else { "-inf" }.to_string()
+        } else if self.0 == 0.0 {
+            "0".to_string()
+        } else {
+            self.to_string()
+        }
+    }
+
+    /// Raw bit representation of the underlying value
+    pub fn to_bits(self) -> u64 {
+        self.0.to_bits()
+    }
+
+    /// Constructs a Float from its raw bit representation
+    pub fn from_bits(b: u64) -> Self {
+        Self(UnderlyingFloat::from_bits(b))
+    }
+
+    /// Exact equality of the underlying values, without epsilon tolerance
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// assert!(Float::raw_eq(Float(0.1), Float(0.1)));
+    /// assert!(!Float::raw_eq(Float(0.1), Float(0.1000001)));
+    /// ```
+    pub fn raw_eq(a: Float, b: Float) -> bool {
+        a.0 == b.0
+    }
+
+    /// Ordering of the underlying values, without epsilon tolerance
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// use std::cmp::Ordering;
+    /// assert_eq!(Float::raw_cmp(Float(0.1), Float(0.1000001)), Some(Ordering::Less));
+    /// assert_eq!(Float::raw_cmp(Float(f64::NAN), Float(0.0)), None);
+    /// ```
+    pub fn raw_cmp(a: Float, b: Float) -> Option<std::cmp::Ordering> {
+        a.0.partial_cmp(&b.0)
+    }
+
+    /// Total ordering of the underlying values, without epsilon tolerance
+    ///
+    /// Follows f64::total_cmp, so NaN sorts after positive infinity.
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// let mut v = vec![Float(2.0), Float(f64::NAN), Float(-1.0)];
+    /// v.sort_by(|a, b| a.total_cmp(*b));
+    /// assert_eq!(v[0], Float(-1.0));
+    /// assert!(v[2].0.is_nan());
+    /// ```
+    pub fn total_cmp(self, other: Float) -> std::cmp::Ordering {
+        self.0.total_cmp(&other.0)
+    }
+
+    pub fn zero() -> Self {
+        Self(0.0)
+    }
+
+    pub fn one() -> Self {
+        Self(1.0)
+    }
+
+    /// Sums a slice of Floats
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// assert_eq!(Float::sum_slice(&[Float(1.0), Float(2.5)]), Float(3.5));
+    /// assert_eq!(Float::sum_slice(&[]), Float(0.0));
+    /// ```
+    pub fn sum_slice(slice: &[Float]) -> Float {
+        slice.iter().copied().sum()
+    }
+
+    /// Adds src element-wise onto dst in place
+    ///
+    /// Panics if the lengths differ.
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// let mut dst = [Float(1.0), Float(2.0)];
+    /// Float::add_slice_assign(&mut dst, &[Float(0.5), Float(-1.0)]);
+    /// assert_eq!(dst, [Float(1.5), Float(1.0)]);
+    /// ```
+    pub fn add_slice_assign(dst: &mut [Float], src: &[Float]) {
+        assert_eq!(dst.len(), src.len(), "slice lengths differ");
+        dst.iter_mut().zip(src).for_each(|(d, s)| *d += *s);
+    }
+
+    /// Yields n evenly spaced values from start to end inclusive
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// let values: Vec<_> = Float::linspace(Float(0.0), Float(1.0), 3).collect();
+    /// assert_eq!(values, [Float(0.0), Float(0.5), Float(1.0)]);
+    /// let values: Vec<_> = Float::linspace(Float(2.0), Float(3.0), 1).collect();
+    /// assert_eq!(values, [Float(2.0)]);
+    /// assert_eq!(Float::linspace(Float(2.0), Float(3.0), 0).count(), 0);
+    /// ```
+    pub fn linspace(start: Float, end: Float, n: usize) -> impl Iterator<Item = Float> {
+        let step = if n > 1 {
+            (end - start) / Float((n - 1) as UnderlyingFloat)
+        } else {
+            Float(0.0)
+        };
+        (0..n).map(move |i| {
+            if i + 1 == n && n > 1 {
+                end
+            } else {
+                start + step * Float(i as UnderlyingFloat)
+            }
+        })
+    }
 }
 
 impl PartialEq for Float {
@@ -27,7 +542,7 @@ impl PartialEq for Float {
     /// assert_ne!(a, b);
     /// ```
     fn eq(&self, other: &Self) -> bool {
-        (self.0 - other.0).abs() < EPSILON
+        self.approx_eq(*other, EPSILON)
     }
 }
 
@@ -52,6 +567,28 @@ impl PartialEq<UnderlyingFloat> for Float {
     }
 }
 
+/// Float is treated as Eq for use as a map key, although the epsilon comparison is not
+/// transitive and NaN never equals itself
+impl Eq for Float {}
+
+impl std::hash::Hash for Float {
+    /// Hashes the value quantized to buckets of EPSILON width
+    ///
+    /// Rounding to the nearest bucket makes values that compare equal hash identically in
+    /// almost all cases. Two values less than EPSILON apart can still fall on either side of
+    /// a bucket boundary and hash differently.
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// use std::collections::HashSet;
+    /// let set: HashSet<Float> = [Float(0.5), Float(0.5000001), Float(0.7)].into_iter().collect();
+    /// assert_eq!(set.len(), 2);
+    /// ```
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        ((self.0 / EPSILON).round() as i64).hash(state);
+    }
+}
+
 impl PartialOrd for Float {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         if self == other {
@@ -152,11 +689,427 @@ impl std::ops::Div for Float {
     }
 }
 
+impl std::ops::MulAssign<UnderlyingFloat> for Float {
+    /// MulAssign with Scalar as underlying type
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// let mut a = Float(-2.0);
+    /// a *= 3.5;
+    /// assert_eq!(a, Float(-7.0));
+    /// ```
+    fn mul_assign(&mut self, rhs: UnderlyingFloat) {
+        self.0 *= rhs;
+    }
+}
+
+impl std::ops::Mul<UnderlyingFloat> for Float {
+    type Output = Self;
+    /// Mul with Scalar as underlying type
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// let a = Float(-2.0);
+    /// assert_eq!(a * 0.5, Float(-1.0));
+    /// ```
+    fn mul(self, rhs: UnderlyingFloat) -> Self::Output {
+        let mut res = self;
+        res *= rhs;
+        res
+    }
+}
+
+impl std::ops::DivAssign<UnderlyingFloat> for Float {
+    /// DivAssign by Scalar as underlying type
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// let mut a = Float(3.0);
+    /// a /= 2.0;
+    /// assert_eq!(a, Float(1.5));
+    /// ```
+    fn div_assign(&mut self, rhs: UnderlyingFloat) {
+        self.0 /= rhs;
+    }
+}
+
+impl std::ops::Div<UnderlyingFloat> for Float {
+    type Output = Self;
+    /// Div by Scalar as underlying type
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// let a = Float(3.0);
+    /// assert_eq!(a / 2.0, Float(1.5));
+    /// ```
+    fn div(self, rhs: UnderlyingFloat) -> Self::Output {
+        let mut res = self;
+        res /= rhs;
+        res
+    }
+}
+
 impl std::iter::Sum<Float> for Float {
     fn sum<I: Iterator<Item = Float>>(iter: I) -> Self {
         Self(iter.map(|f| f.0).sum())
     }
 }
 
+#[cfg(feature = "num-traits")]
+impl num_traits::Zero for Float {
+    fn zero() -> Self {
+        Float::zero()
+    }
+
+    /// Checks for zero within EPSILON, like PartialEq
+    fn is_zero(&self) -> bool {
+        *self == 0.0
+    }
+}
+
+#[cfg(feature = "num-traits")]
+impl num_traits::One for Float {
+    fn one() -> Self {
+        Float::one()
+    }
+}
+
+impl From<UnderlyingFloat> for Float {
+    /// Wraps an underlying float
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// let f: Float = 3.0.into();
+    /// assert_eq!(f, Float(3.0));
+    /// ```
+    fn from(value: UnderlyingFloat) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Float> for UnderlyingFloat {
+    /// Unwraps to the underlying float
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// let x: f64 = Float(3.0).into();
+    /// assert_eq!(x, 3.0);
+    /// ```
+    fn from(value: Float) -> Self {
+        value.0
+    }
+}
+
+impl From<i32> for Float {
+    /// Converts an i32 exactly
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// assert_eq!(Float::from(-3), Float(-3.0));
+    /// ```
+    fn from(value: i32) -> Self {
+        Self(value.into())
+    }
+}
+
+impl From<u8> for Float {
+    /// Converts a u8 exactly
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// assert_eq!(Float::from(255u8) / 255.0, Float(1.0));
+    /// ```
+    fn from(value: u8) -> Self {
+        Self(value.into())
+    }
+}
+
+impl From<i64> for Float {
+    /// Converts an i64, rounding to the nearest representable value
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// assert_eq!(Float::from(7i64), Float(7.0));
+    /// ```
+    fn from(value: i64) -> Self {
+        Self(value as UnderlyingFloat)
+    }
+}
+
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trigonometry() {
+        use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, FRAC_PI_6, PI};
+        assert_eq!(Float(PI).sin(), 0.0);
+        assert_eq!(Float(FRAC_PI_6).sin(), 0.5);
+        assert_eq!(Float(PI).cos(), -1.0);
+        assert_eq!(Float(FRAC_PI_2).cos(), 0.0);
+        assert_eq!(Float(FRAC_PI_4).tan(), 1.0);
+        assert_eq!(Float(1.0).asin(), FRAC_PI_2);
+        assert_eq!(Float(0.5).acos(), PI / 3.0);
+        assert_eq!(Float(1.0).atan(), FRAC_PI_4);
+        assert_eq!(Float(-1.0).atan2(Float(0.0)), -FRAC_PI_2);
+    }
+
+    #[test]
+    fn exponentials() {
+        use std::f64::consts::E;
+        assert_eq!(Float(0.0).exp(), 1.0);
+        assert_eq!(Float(1.0).exp(), E);
+        assert_eq!(Float(E).ln(), 1.0);
+        assert_eq!(Float(1.0).ln(), 0.0);
+        assert_eq!(Float(2.5).exp().ln(), 2.5);
+    }
+
+    #[test]
+    fn clamp_assign() {
+        let clamped = |v| {
+            let mut f = Float(v);
+            f.clamp_assign(Float(-1.0), Float(2.0));
+            f
+        };
+        assert_eq!(clamped(-3.0), -1.0);
+        assert_eq!(clamped(0.5), 0.5);
+        assert_eq!(clamped(2.5), 2.0);
+    }
+
+    #[test]
+    fn sign_and_magnitude() {
+        assert_eq!(Float(-2.5).abs(), 2.5);
+        assert_eq!(Float(2.5).abs(), 2.5);
+        assert_eq!(Float(0.0).abs(), 0.0);
+        assert_eq!(Float(-2.5).signum(), -1.0);
+        assert_eq!(Float(2.5).signum(), 1.0);
+        assert_eq!(Float(0.0).signum(), 1.0);
+        assert_eq!(Float(-0.0).signum(), -1.0);
+        assert_eq!(Float(2.0).copysign(Float(-1.0)), -2.0);
+        assert_eq!(Float(-2.0).copysign(Float(1.0)), 2.0);
+        assert_eq!(Float(-2.0).copysign(Float(0.0)), 2.0);
+    }
+
+    #[test]
+    fn rounding() {
+        for (v, floor, ceil, round, trunc) in [
+            (2.7, 2.0, 3.0, 3.0, 2.0),
+            (-2.3, -3.0, -2.0, -2.0, -2.0),
+            (4.0, 4.0, 4.0, 4.0, 4.0),
+        ] {
+            assert_eq!(Float(v).floor(), floor);
+            assert_eq!(Float(v).ceil(), ceil);
+            assert_eq!(Float(v).round(), round);
+            assert_eq!(Float(v).trunc(), trunc);
+            assert_eq!(Float(v).fract(), v - trunc);
+        }
+    }
+
+    #[test]
+    fn zero_one() {
+        assert_eq!(Float::zero() + Float::one(), Float::one());
+        assert_eq!(Float::one() * Float(3.5), Float(3.5));
+        assert_eq!(Float::zero(), 0.0);
+    }
+
+    #[cfg(feature = "num-traits")]
+    #[test]
+    fn num_traits_zero_one() {
+        use num_traits::{One, Zero};
+        assert_eq!(<Float as Zero>::zero(), Float::zero());
+        assert_eq!(<Float as One>::one(), Float::one());
+        assert!(Zero::is_zero(&Float(EPSILON / 2.0)));
+        assert!(!Zero::is_zero(&Float::one()));
+    }
+
+    #[test]
+    fn sum_slice() {
+        let values = [Float(0.25), Float(-1.0), Float(3.0)];
+        assert_eq!(Float::sum_slice(&values), 2.25);
+        assert_eq!(Float::sum_slice(&values[..1]), 0.25);
+    }
+
+    #[test]
+    fn add_slice_assign() {
+        let mut dst = vec![Float(1.0); 3];
+        Float::add_slice_assign(&mut dst, &[Float(1.0), Float(2.0), Float(3.0)]);
+        Float::add_slice_assign(&mut dst, &[Float(-0.5); 3]);
+        assert_eq!(dst, [Float(1.5), Float(2.5), Float(3.5)]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn add_slice_assign_length_mismatch() {
+        let mut dst = [Float(0.0); 2];
+        Float::add_slice_assign(&mut dst, &[Float(1.0)]);
+    }
+
+    #[test]
+    fn display_canonical() {
+        assert_eq!(format!("{}", Float(-0.0)), "-0");
+        assert_eq!(Float(-0.0).display_canonical(), "0");
+        assert_eq!(Float(0.0).display_canonical(), "0");
+        assert_eq!(Float(UnderlyingFloat::NAN).display_canonical(), "nan");
+        assert_eq!(Float(-UnderlyingFloat::NAN).display_canonical(), "nan");
+        assert_eq!(Float(UnderlyingFloat::INFINITY).display_canonical(), "inf");
+        assert_eq!(Float(-2.5).display_canonical(), "-2.5");
+    }
+
+    #[test]
+    fn conversions() {
+        let f: Float = 2.5.into();
+        assert_eq!(f, Float(2.5));
+        let x: UnderlyingFloat = f.into();
+        assert_eq!(x, 2.5);
+        assert_eq!(Float::from(-4), Float(-4.0));
+        assert_eq!(Float::from(128u8), Float(128.0));
+        assert_eq!(
+            Float::from(1i64 << 40),
+            Float((1i64 << 40) as UnderlyingFloat)
+        );
+    }
+
+    #[test]
+    fn clamp() {
+        let (lo, hi) = (Float(-1.0), Float(2.0));
+        assert_eq!(Float(-3.0).clamp(lo, hi), -1.0);
+        assert_eq!(Float(0.5).clamp(lo, hi), 0.5);
+        assert_eq!(Float(2.5).clamp(lo, hi), 2.0);
+        assert!(Float(UnderlyingFloat::NAN).clamp(lo, hi).0.is_nan());
+    }
+
+    #[test]
+    fn min_max() {
+        assert_eq!(Float(-1.0).min(Float(3.0)), -1.0);
+        assert_eq!(Float(-1.0).max(Float(3.0)), 3.0);
+        assert_eq!(Float(3.0).min(Float(UnderlyingFloat::NAN)), 3.0);
+        assert_eq!(Float(3.0).max(Float(UnderlyingFloat::NAN)), 3.0);
+    }
+
+    #[test]
+    fn saturate_assign() {
+        let saturated = |v| {
+            let mut f = Float(v);
+            f.saturate_assign();
+            f
+        };
+        assert_eq!(saturated(-0.5), 0.0);
+        assert_eq!(saturated(0.3), 0.3);
+        assert_eq!(saturated(1.7), 1.0);
+    }
+
+    #[test]
+    fn bits_round_trip() {
+        let subnormal = f64::MIN_POSITIVE / 4.0;
+        assert!(subnormal.is_subnormal());
+        for value in [0.0, -0.0, 1.5, -123.456, subnormal, f64::INFINITY] {
+            let bits = Float(value).to_bits();
+            assert_eq!(bits, value.to_bits());
+            assert_eq!(Float::from_bits(bits).to_bits(), bits);
+        }
+        assert!(Float::from_bits(Float(-0.0).to_bits()).0.is_sign_negative());
+        assert_ne!(Float(-0.0).to_bits(), Float(0.0).to_bits());
+        assert!(Float::from_bits(Float(f64::NAN).to_bits()).0.is_nan());
+    }
+
+    #[test]
+    fn hash_matches_eq() {
+        use std::collections::HashMap;
+        use std::hash::{BuildHasher, RandomState};
+        let state = RandomState::new();
+        assert_eq!(
+            state.hash_one(Float(1.0)),
+            state.hash_one(Float(1.0 + EPSILON / 10.0))
+        );
+        assert_eq!(state.hash_one(Float(0.0)), state.hash_one(Float(-0.0)));
+        assert_ne!(state.hash_one(Float(1.0)), state.hash_one(Float(1.1)));
+
+        let mut palette: HashMap<Float, usize> = HashMap::new();
+        for t in [0.25, 0.2500001, 0.75, 0.2499999] {
+            *palette.entry(Float(t)).or_default() += 1;
+        }
+        assert_eq!(palette.len(), 2);
+        assert_eq!(palette[&Float(0.25)], 3);
+    }
+
+    #[test]
+    fn approx_eq_large_magnitudes() {
+        let a = Float(2.5e6);
+        let b = Float(2.5e6 + 0.01);
+        assert_ne!(a, b);
+        assert!(a.approx_eq(b, 0.1));
+        assert!(a.approx_eq_relative(b, 1e-8));
+        assert!(!a.approx_eq_relative(b, 1e-10));
+
+        let c = Float(1e15);
+        let d = Float(1e15 + 0.125);
+        assert_ne!(c, d);
+        assert!(c.approx_eq_relative(d, 1e-12));
+    }
+
+    #[test]
+    fn approx_eq_small_magnitudes() {
+        let a = Float(1e-7);
+        let b = Float(2e-7);
+        assert_eq!(a, b);
+        assert!(a.approx_eq(b, EPSILON));
+        assert!(!a.approx_eq(b, 1e-8));
+        assert!(!a.approx_eq_relative(b, 0.1));
+        assert!(Float(0.0).approx_eq_relative(Float(0.0), 0.0));
+    }
+
+    #[test]
+    fn close_enough() {
+        let (abs_tol, rel_tol) = (1e-9, 1e-9);
+        assert!(Float(0.0).close_enough(Float(5e-10), abs_tol, rel_tol));
+        assert!(!Float(0.0).close_enough(Float(5e-9), abs_tol, rel_tol));
+        assert!(!Float(1e-12).approx_eq_relative(Float(2e-12), rel_tol));
+        assert!(Float(1e-12).close_enough(Float(2e-12), abs_tol, rel_tol));
+        assert!(Float(1e9).close_enough(Float(1e9 + 0.5), abs_tol, rel_tol));
+        assert!(!Float(1e9).close_enough(Float(1e9 + 2.0), abs_tol, rel_tol));
+        assert!(!Float(1e9).approx_eq(Float(1e9 + 0.5), abs_tol));
+    }
+
+    #[test]
+    fn raw_eq_is_exact() {
+        let a = Float(1.0);
+        let b = Float(1.0 + EPSILON / 10.0);
+        assert_eq!(a, b);
+        assert!(!Float::raw_eq(a, b));
+        assert!(Float::raw_eq(a, a));
+        assert!(!Float::raw_eq(Float(f64::NAN), Float(f64::NAN)));
+    }
+
+    #[test]
+    fn total_cmp_sorts_with_nan() {
+        let mut v: Vec<Float> = [
+            3.0,
+            UnderlyingFloat::NAN,
+            -2.0,
+            0.5,
+            UnderlyingFloat::INFINITY,
+        ]
+        .into_iter()
+        .map(Float)
+        .collect();
+        v.sort_by(|a, b| a.total_cmp(*b));
+        let underlying: Vec<UnderlyingFloat> = v[..4].iter().map(|f| f.0).collect();
+        assert_eq!(underlying, [-2.0, 0.5, 3.0, UnderlyingFloat::INFINITY]);
+        assert!(v[4].0.is_nan());
+        assert_eq!(
+            Float(0.1).total_cmp(Float(0.1000001)),
+            std::cmp::Ordering::Less
+        );
+    }
+
+    #[test]
+    fn raw_cmp_is_exact() {
+        use std::cmp::Ordering;
+        let a = Float(1.0);
+        let b = Float(1.0 + EPSILON / 10.0);
+        assert_eq!(a.partial_cmp(&b), Some(Ordering::Equal));
+        assert_eq!(Float::raw_cmp(a, b), Some(Ordering::Less));
+        assert_eq!(Float::raw_cmp(b, a), Some(Ordering::Greater));
+        assert_eq!(Float::raw_cmp(a, a), Some(Ordering::Equal));
+    }
+}
@@ -0,0 +1,105 @@
+use crate::prelude::*;
+
+/// Distance t along a ray at which it hits object
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Intersection<'a> {
+    pub t: Float,
+    pub object: &'a Sphere,
+}
+
+impl<'a> Intersection<'a> {
+    pub fn new(t: Float, object: &'a Sphere) -> Self {
+        Self { t, object }
+    }
+}
+
+/// Selects the visible intersection, the one with the smallest non-negative t
+///
+/// Among equal t the first one wins.
+///
+/// ```
+/// use rtlib::float::Float;
+/// use rtlib::intersection::{hit, Intersection};
+/// use rtlib::shapes::sphere::Sphere;
+/// let s = Sphere::default();
+/// let xs = [Intersection::new(Float(-1.0), &s), Intersection::new(Float(1.0), &s)];
+/// assert_eq!(hit(&xs), Some(&xs[1]));
+/// ```
+pub fn hit<'a, 'b>(intersections: &'b [Intersection<'a>]) -> Option<&'b Intersection<'a>> {
+    intersections
+        .iter()
+        .filter(|i| i.t.0 >= 0.0)
+        .min_by(|a, b| a.t.total_cmp(b.t))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_positive() {
+        let s = Sphere::default();
+        let xs = [
+            Intersection::new(Float(1.0), &s),
+            Intersection::new(Float(2.0), &s),
+        ];
+        assert_eq!(hit(&xs), Some(&xs[0]));
+    }
+
+    #[test]
+    fn some_negative() {
+        let s = Sphere::default();
+        let xs = [
+            Intersection::new(Float(-1.0), &s),
+            Intersection::new(Float(1.0), &s),
+        ];
+        assert_eq!(hit(&xs), Some(&xs[1]));
+    }
+
+    #[test]
+    fn all_negative() {
+        let s = Sphere::default();
+        let xs = [
+            Intersection::new(Float(-2.0), &s),
+            Intersection::new(Float(-1.0), &s),
+        ];
+        assert_eq!(hit(&xs), None);
+        assert_eq!(hit(&[]), None);
+    }
+
+    #[test]
+    fn lowest_non_negative() {
+        let s = Sphere::default();
+        let xs = [
+            Intersection::new(Float(5.0), &s),
+            Intersection::new(Float(7.0), &s),
+            Intersection::new(Float(-3.0), &s),
+            Intersection::new(Float(2.0), &s),
+        ];
+        assert!(std::ptr::eq(hit(&xs).unwrap(), &xs[3]));
+    }
+
+    #[test]
+    fn ties_prefer_first() {
+        let a = Sphere::default();
+        let b = Sphere::default();
+        let xs = [
+            Intersection::new(Float(2.0), &a),
+            Intersection::new(Float(2.0), &b),
+        ];
+        assert!(std::ptr::eq(hit(&xs).unwrap(), &xs[0]));
+    }
+
+    #[test]
+    fn sphere_intersections() {
+        let s = Sphere::default();
+        let r = Ray::new(
+            Tuple::point_from_underlying(0.0, 0.0, -5.0),
+            Tuple::vector_from_underlying(0.0, 0.0, 1.0),
+        );
+        let xs = s.intersections(&r);
+        assert_eq!(xs.len(), 2);
+        assert!(xs.iter().all(|i| std::ptr::eq(i.object, &s)));
+        assert_eq!(hit(&xs).unwrap().t, 4.0);
+    }
+}
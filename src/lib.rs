@@ -1,6 +1,10 @@
 pub mod canvas;
 pub mod color;
 pub mod float;
+pub mod intersection;
+pub mod matrix;
+pub mod ray;
+pub mod shapes;
 pub mod tuple;
 
 pub mod prelude {
@@ -11,5 +15,9 @@ pub mod prelude {
     pub use crate::canvas::Canvas;
     pub use crate::color::Color;
     pub use crate::float::Float;
+    pub use crate::intersection::Intersection;
+    pub use crate::matrix::Matrix4;
+    pub use crate::ray::Ray;
+    pub use crate::shapes::sphere::Sphere;
     pub use crate::tuple::Tuple;
 }
@@ -0,0 +1,530 @@
+pub mod transform;
+
+use crate::prelude::*;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Matrix4 {
+    pub elements: [[Float; 4]; 4],
+}
+
+impl Matrix4 {
+    pub fn new(rows: [[Float; 4]; 4]) -> Self {
+        Self { elements: rows }
+    }
+
+    /// Constructs a Matrix4 from rows of underlying types
+    ///
+    /// ```
+    /// use rtlib::matrix::Matrix4;
+    /// let m = Matrix4::new_from_underlying([
+    ///     [1.0, 2.0, 3.0, 4.0],
+    ///     [5.5, 6.5, 7.5, 8.5],
+    ///     [9.0, 10.0, 11.0, 12.0],
+    ///     [13.5, 14.5, 15.5, 16.5],
+    /// ]);
+    /// assert_eq!(m[(0, 3)], 4.0);
+    /// assert_eq!(m[(1, 0)], 5.5);
+    /// assert_eq!(m[(3, 2)], 15.5);
+    /// ```
+    pub fn new_from_underlying(rows: [[UnderlyingFloat; 4]; 4]) -> Self {
+        Self::new(rows.map(|row| row.map(Float)))
+    }
+
+    /// Yields the identity matrix
+    ///
+    /// ```
+    /// use rtlib::matrix::Matrix4;
+    /// use rtlib::tuple::Tuple;
+    /// let a = Tuple::new_from_underlying(1.0, 2.0, 3.0, 4.0);
+    /// assert_eq!(Matrix4::identity() * a, a);
+    /// ```
+    pub fn identity() -> Self {
+        let mut res = Self::new([[Float(0.0); 4]; 4]);
+        (0..4).for_each(|i| res[(i, i)] = Float(1.0));
+        res
+    }
+
+    /// Swaps rows and columns
+    ///
+    /// ```
+    /// use rtlib::matrix::Matrix4;
+    /// let a = Matrix4::new_from_underlying([
+    ///     [0.0, 9.0, 3.0, 0.0],
+    ///     [9.0, 8.0, 0.0, 8.0],
+    ///     [1.0, 8.0, 5.0, 3.0],
+    ///     [0.0, 0.0, 5.0, 8.0],
+    /// ]);
+    /// assert_eq!(
+    ///     a.transpose(),
+    ///     Matrix4::new_from_underlying([
+    ///         [0.0, 9.0, 1.0, 0.0],
+    ///         [9.0, 8.0, 8.0, 0.0],
+    ///         [3.0, 0.0, 5.0, 5.0],
+    ///         [0.0, 8.0, 3.0, 8.0],
+    ///     ])
+    /// );
+    /// assert_eq!(Matrix4::identity().transpose(), Matrix4::identity());
+    /// ```
+    pub fn transpose(&self) -> Matrix4 {
+        let mut res = *self;
+        for row in 0..4 {
+            for col in 0..4 {
+                res[(row, col)] = self[(col, row)];
+            }
+        }
+        res
+    }
+
+    /// Follows self by a translation
+    ///
+    /// The builder methods apply in reading order: each transform is applied after all
+    /// previously chained ones, so the last called is applied last.
+    ///
+    /// ```
+    /// use rtlib::matrix::Matrix4;
+    /// use rtlib::float::Float;
+    /// use rtlib::tuple::Tuple;
+    /// let t = Matrix4::identity()
+    ///     .scaled(Float(2.0), Float(2.0), Float(2.0))
+    ///     .translated(Float(1.0), Float(0.0), Float(0.0));
+    /// let p = Tuple::point_from_underlying(1.0, 1.0, 1.0);
+    /// assert_eq!(t * p, Tuple::point_from_underlying(3.0, 2.0, 2.0));
+    /// ```
+    pub fn translated(self, x: Float, y: Float, z: Float) -> Matrix4 {
+        transform::translation(x, y, z) * self
+    }
+
+    /// Follows self by a scaling
+    pub fn scaled(self, x: Float, y: Float, z: Float) -> Matrix4 {
+        transform::scaling(x, y, z) * self
+    }
+
+    /// Follows self by a rotation around the x axis
+    pub fn rotated_x(self, r: Float) -> Matrix4 {
+        transform::rotation_x(r) * self
+    }
+
+    /// Follows self by a rotation around the y axis
+    pub fn rotated_y(self, r: Float) -> Matrix4 {
+        transform::rotation_y(r) * self
+    }
+
+    /// Follows self by a rotation around the z axis
+    pub fn rotated_z(self, r: Float) -> Matrix4 {
+        transform::rotation_z(r) * self
+    }
+
+    /// Follows self by a shearing
+    pub fn sheared(
+        self,
+        xy: Float,
+        xz: Float,
+        yx: Float,
+        yz: Float,
+        zx: Float,
+        zy: Float,
+    ) -> Matrix4 {
+        transform::shearing(xy, xz, yx, yz, zx, zy) * self
+    }
+
+    fn determinant2(m: [[Float; 2]; 2]) -> Float {
+        m[0][0] * m[1][1] - m[0][1] * m[1][0]
+    }
+
+    fn submatrix3(m: [[Float; 3]; 3], row: usize, col: usize) -> [[Float; 2]; 2] {
+        let mut res = [[Float(0.0); 2]; 2];
+        for (dst_row, src_row) in (0..3).filter(|r| *r != row).enumerate() {
+            for (dst_col, src_col) in (0..3).filter(|c| *c != col).enumerate() {
+                res[dst_row][dst_col] = m[src_row][src_col];
+            }
+        }
+        res
+    }
+
+    fn determinant3(m: [[Float; 3]; 3]) -> Float {
+        (0..3)
+            .map(|col| {
+                let minor = Self::determinant2(Self::submatrix3(m, 0, col));
+                let cofactor = if col.is_multiple_of(2) { minor } else { -minor };
+                m[0][col] * cofactor
+            })
+            .sum()
+    }
+
+    fn submatrix(&self, row: usize, col: usize) -> [[Float; 3]; 3] {
+        let mut res = [[Float(0.0); 3]; 3];
+        for (dst_row, src_row) in (0..4).filter(|r| *r != row).enumerate() {
+            for (dst_col, src_col) in (0..4).filter(|c| *c != col).enumerate() {
+                res[dst_row][dst_col] = self[(src_row, src_col)];
+            }
+        }
+        res
+    }
+
+    fn cofactor(&self, row: usize, col: usize) -> Float {
+        let minor = Self::determinant3(self.submatrix(row, col));
+        if (row + col).is_multiple_of(2) {
+            minor
+        } else {
+            -minor
+        }
+    }
+
+    /// Calculates the determinant by cofactor expansion along the first row
+    ///
+    /// ```
+    /// use rtlib::matrix::Matrix4;
+    /// let a = Matrix4::new_from_underlying([
+    ///     [-2.0, -8.0, 3.0, 5.0],
+    ///     [-3.0, 1.0, 7.0, 3.0],
+    ///     [1.0, 2.0, -9.0, 6.0],
+    ///     [-6.0, 7.0, 7.0, -9.0],
+    /// ]);
+    /// assert_eq!(a.determinant(), -4071.0);
+    /// ```
+    pub fn determinant(&self) -> Float {
+        (0..4)
+            .map(|col| self[(0, col)] * self.cofactor(0, col))
+            .sum()
+    }
+
+    /// Checks whether the determinant is not within EPSILON of 0
+    pub fn is_invertible(&self) -> bool {
+        self.determinant() != 0.0
+    }
+
+    /// Calculates the inverse, or None if the matrix is singular
+    ///
+    /// ```
+    /// use rtlib::matrix::Matrix4;
+    /// let singular = Matrix4::new_from_underlying([
+    ///     [-4.0, 2.0, -2.0, -3.0],
+    ///     [9.0, 6.0, 2.0, 6.0],
+    ///     [0.0, -5.0, 1.0, -5.0],
+    ///     [0.0, 0.0, 0.0, 0.0],
+    /// ]);
+    /// assert_eq!(singular.inverse(), None);
+    /// assert_eq!(Matrix4::identity().inverse(), Some(Matrix4::identity()));
+    /// ```
+    pub fn inverse(&self) -> Option<Matrix4> {
+        let determinant = self.determinant();
+        if determinant == 0.0 {
+            return None;
+        }
+        let mut res = *self;
+        for row in 0..4 {
+            for col in 0..4 {
+                res[(col, row)] = self.cofactor(row, col) / determinant;
+            }
+        }
+        Some(res)
+    }
+}
+
+impl std::ops::Index<(usize, usize)> for Matrix4 {
+    type Output = Float;
+    /// Element at (row, column)
+    fn index(&self, index: (usize, usize)) -> &Self::Output {
+        &self.elements[index.0][index.1]
+    }
+}
+
+impl std::ops::IndexMut<(usize, usize)> for Matrix4 {
+    fn index_mut(&mut self, index: (usize, usize)) -> &mut Self::Output {
+        &mut self.elements[index.0][index.1]
+    }
+}
+
+impl std::ops::MulAssign for Matrix4 {
+    /// Multiplies self by rhs from the right
+    fn mul_assign(&mut self, rhs: Self) {
+        let lhs = *self;
+        for row in 0..4 {
+            for col in 0..4 {
+                self[(row, col)] = (0..4).map(|i| lhs[(row, i)] * rhs[(i, col)]).sum();
+            }
+        }
+    }
+}
+
+impl std::ops::Mul for Matrix4 {
+    type Output = Self;
+    /// Muls two matrices
+    ///
+    /// ```
+    /// use rtlib::matrix::Matrix4;
+    /// let a = Matrix4::new_from_underlying([
+    ///     [1.0, 2.0, 3.0, 4.0],
+    ///     [5.0, 6.0, 7.0, 8.0],
+    ///     [9.0, 8.0, 7.0, 6.0],
+    ///     [5.0, 4.0, 3.0, 2.0],
+    /// ]);
+    /// assert_eq!(a * Matrix4::identity(), a);
+    /// ```
+    fn mul(self, rhs: Self) -> Self::Output {
+        let mut res = self;
+        res *= rhs;
+        res
+    }
+}
+
+impl std::ops::Mul<Tuple> for Matrix4 {
+    type Output = Tuple;
+    /// Transforms a Tuple
+    ///
+    /// ```
+    /// use rtlib::matrix::Matrix4;
+    /// use rtlib::tuple::Tuple;
+    /// let a = Matrix4::new_from_underlying([
+    ///     [1.0, 2.0, 3.0, 4.0],
+    ///     [2.0, 4.0, 4.0, 2.0],
+    ///     [8.0, 6.0, 4.0, 1.0],
+    ///     [0.0, 0.0, 0.0, 1.0],
+    /// ]);
+    /// let b = Tuple::new_from_underlying(1.0, 2.0, 3.0, 1.0);
+    /// assert_eq!(a * b, Tuple::new_from_underlying(18.0, 24.0, 33.0, 1.0));
+    /// ```
+    fn mul(self, rhs: Tuple) -> Self::Output {
+        Tuple {
+            elements: self
+                .elements
+                .map(|row| std::iter::zip(row, rhs.elements).map(|(l, r)| l * r).sum()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn equality() {
+        let a = Matrix4::new_from_underlying([
+            [1.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 7.0, 8.0],
+            [9.0, 8.0, 7.0, 6.0],
+            [5.0, 4.0, 3.0, 2.0],
+        ]);
+        let mut b = a;
+        b[(2, 1)] = Float(8.000001);
+        assert_eq!(a, b);
+        b[(2, 1)] = Float(8.1);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn multiply() {
+        let a = Matrix4::new_from_underlying([
+            [1.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 7.0, 8.0],
+            [9.0, 8.0, 7.0, 6.0],
+            [5.0, 4.0, 3.0, 2.0],
+        ]);
+        let b = Matrix4::new_from_underlying([
+            [-2.0, 1.0, 2.0, 3.0],
+            [3.0, 2.0, 1.0, -1.0],
+            [4.0, 3.0, 6.0, 5.0],
+            [1.0, 2.0, 7.0, 8.0],
+        ]);
+        assert_eq!(
+            a * b,
+            Matrix4::new_from_underlying([
+                [20.0, 22.0, 50.0, 48.0],
+                [44.0, 54.0, 114.0, 108.0],
+                [40.0, 58.0, 110.0, 102.0],
+                [16.0, 26.0, 46.0, 42.0],
+            ])
+        );
+        let mut c = a;
+        c *= b;
+        assert_eq!(c, a * b);
+    }
+
+    #[test]
+    fn transpose() {
+        let a = Matrix4::new_from_underlying([
+            [1.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 7.0, 8.0],
+            [9.0, 8.0, 7.0, 6.0],
+            [5.0, 4.0, 3.0, 2.0],
+        ]);
+        let t = a.transpose();
+        for row in 0..4 {
+            for col in 0..4 {
+                assert_eq!(t[(row, col)], a[(col, row)]);
+            }
+        }
+        assert_eq!(t.transpose(), a);
+    }
+
+    #[test]
+    fn submatrix_determinants() {
+        let a = [
+            [Float(1.0), Float(5.0), Float(0.0)],
+            [Float(-3.0), Float(2.0), Float(7.0)],
+            [Float(0.0), Float(6.0), Float(-3.0)],
+        ];
+        assert_eq!(
+            Matrix4::submatrix3(a, 0, 2),
+            [[Float(-3.0), Float(2.0)], [Float(0.0), Float(6.0)]]
+        );
+        assert_eq!(
+            Matrix4::determinant2([[Float(1.0), Float(5.0)], [Float(-3.0), Float(2.0)]]),
+            17.0
+        );
+        let b = [
+            [Float(1.0), Float(2.0), Float(6.0)],
+            [Float(-5.0), Float(8.0), Float(-4.0)],
+            [Float(2.0), Float(6.0), Float(4.0)],
+        ];
+        assert_eq!(Matrix4::determinant3(b), -196.0);
+
+        let c = Matrix4::new_from_underlying([
+            [-2.0, -8.0, 3.0, 5.0],
+            [-3.0, 1.0, 7.0, 3.0],
+            [1.0, 2.0, -9.0, 6.0],
+            [-6.0, 7.0, 7.0, -9.0],
+        ]);
+        assert_eq!(c.cofactor(0, 0), 690.0);
+        assert_eq!(c.cofactor(0, 1), 447.0);
+        assert_eq!(c.cofactor(0, 2), 210.0);
+        assert_eq!(c.cofactor(0, 3), 51.0);
+        assert_eq!(c.determinant(), -4071.0);
+    }
+
+    #[test]
+    fn invertibility() {
+        let a = Matrix4::new_from_underlying([
+            [6.0, 4.0, 4.0, 4.0],
+            [5.0, 5.0, 7.0, 6.0],
+            [4.0, -9.0, 3.0, -7.0],
+            [9.0, 1.0, 7.0, -6.0],
+        ]);
+        assert_eq!(a.determinant(), -2120.0);
+        assert!(a.is_invertible());
+        let b = Matrix4::new_from_underlying([
+            [-4.0, 2.0, -2.0, -3.0],
+            [9.0, 6.0, 2.0, 6.0],
+            [0.0, -5.0, 1.0, -5.0],
+            [0.0, 0.0, 0.0, 0.0],
+        ]);
+        assert_eq!(b.determinant(), 0.0);
+        assert!(!b.is_invertible());
+        assert_eq!(b.inverse(), None);
+    }
+
+    #[test]
+    fn inverse() {
+        let a = Matrix4::new_from_underlying([
+            [-5.0, 2.0, 6.0, -8.0],
+            [1.0, -5.0, 1.0, 8.0],
+            [7.0, 7.0, -6.0, -7.0],
+            [1.0, -3.0, 7.0, 4.0],
+        ]);
+        let b = a.inverse().unwrap();
+        assert_eq!(a.determinant(), 532.0);
+        assert_eq!(a.cofactor(2, 3), -160.0);
+        assert_eq!(b[(3, 2)], -160.0 / 532.0);
+        assert_eq!(a.cofactor(3, 2), 105.0);
+        assert_eq!(b[(2, 3)], 105.0 / 532.0);
+        assert_eq!(
+            b,
+            Matrix4::new_from_underlying([
+                [0.21805, 0.45113, 0.24060, -0.04511],
+                [-0.80827, -1.45677, -0.44361, 0.52068],
+                [-0.07895, -0.22368, -0.05263, 0.19737],
+                [-0.52256, -0.81391, -0.30075, 0.30639],
+            ])
+        );
+
+        let c = Matrix4::new_from_underlying([
+            [8.0, -5.0, 9.0, 2.0],
+            [7.0, 5.0, 6.0, 1.0],
+            [-6.0, 0.0, 9.0, 6.0],
+            [-3.0, 0.0, -9.0, -4.0],
+        ]);
+        assert_eq!(
+            c.inverse().unwrap(),
+            Matrix4::new_from_underlying([
+                [-0.15385, -0.15385, -0.28205, -0.53846],
+                [-0.07692, 0.12308, 0.02564, 0.03077],
+                [0.35897, 0.35897, 0.43590, 0.92308],
+                [-0.69231, -0.69231, -0.76923, -1.92308],
+            ])
+        );
+
+        let d = Matrix4::new_from_underlying([
+            [9.0, 3.0, 0.0, 9.0],
+            [-5.0, -2.0, -6.0, -3.0],
+            [-4.0, 9.0, 6.0, 4.0],
+            [-7.0, 6.0, 6.0, 2.0],
+        ]);
+        assert_eq!(
+            d.inverse().unwrap(),
+            Matrix4::new_from_underlying([
+                [-0.04074, -0.07778, 0.14444, -0.22222],
+                [-0.07778, 0.03333, 0.36667, -0.33333],
+                [-0.02901, -0.14630, -0.10926, 0.12963],
+                [0.17778, 0.06667, -0.26667, 0.33333],
+            ])
+        );
+    }
+
+    #[test]
+    fn inverse_round_trip() {
+        let a = Matrix4::new_from_underlying([
+            [3.0, -9.0, 7.0, 3.0],
+            [3.0, -8.0, 2.0, -9.0],
+            [-4.0, 4.0, 4.0, 1.0],
+            [-6.0, 5.0, -1.0, 1.0],
+        ]);
+        let b = Matrix4::new_from_underlying([
+            [8.0, 2.0, 2.0, 2.0],
+            [3.0, -1.0, 7.0, 0.0],
+            [7.0, 0.0, 5.0, 4.0],
+            [6.0, -2.0, 0.0, 5.0],
+        ]);
+        assert_eq!(a * a.inverse().unwrap(), Matrix4::identity());
+        assert_eq!((a * b) * b.inverse().unwrap(), a);
+    }
+
+    #[test]
+    fn fluent_transforms() {
+        let p = Tuple::point_from_underlying(1.0, 0.0, 1.0);
+        let t = Matrix4::identity()
+            .rotated_x(Float(std::f64::consts::FRAC_PI_2))
+            .scaled(Float(5.0), Float(5.0), Float(5.0))
+            .translated(Float(10.0), Float(5.0), Float(7.0));
+        assert_eq!(t * p, Tuple::point_from_underlying(15.0, 0.0, 7.0));
+        assert_eq!(
+            t,
+            transform::translation(Float(10.0), Float(5.0), Float(7.0))
+                * transform::scaling(Float(5.0), Float(5.0), Float(5.0))
+                * transform::rotation_x(Float(std::f64::consts::FRAC_PI_2))
+        );
+
+        let zero = Float(0.0);
+        let sheared = Matrix4::identity()
+            .sheared(Float(1.0), zero, zero, zero, zero, zero)
+            .rotated_z(Float(std::f64::consts::FRAC_PI_2))
+            .rotated_y(Float(std::f64::consts::PI));
+        assert_eq!(
+            sheared * Tuple::point_from_underlying(2.0, 3.0, 4.0),
+            Tuple::point_from_underlying(3.0, 5.0, -4.0)
+        );
+    }
+
+    #[test]
+    fn identity() {
+        let a = Matrix4::new_from_underlying([
+            [0.0, 1.0, 2.0, 4.0],
+            [1.0, 2.0, 4.0, 8.0],
+            [2.0, 4.0, 8.0, 16.0],
+            [4.0, 8.0, 16.0, 32.0],
+        ]);
+        assert_eq!(a * Matrix4::identity(), a);
+        assert_eq!(Matrix4::identity() * a, a);
+        let t = Tuple::new_from_underlying(1.0, 2.0, 3.0, 4.0);
+        assert_eq!(Matrix4::identity() * t, t);
+    }
+}
@@ -0,0 +1,290 @@
+use crate::prelude::*;
+
+/// Moves points by x, y, z, leaving vectors unchanged
+///
+/// ```
+/// use rtlib::matrix::transform::translation;
+/// use rtlib::float::Float;
+/// use rtlib::tuple::Tuple;
+/// let t = translation(Float(5.0), Float(-3.0), Float(2.0));
+/// let p = Tuple::point_from_underlying(-3.0, 4.0, 5.0);
+/// assert_eq!(t * p, Tuple::point_from_underlying(2.0, 1.0, 7.0));
+/// ```
+pub fn translation(x: Float, y: Float, z: Float) -> Matrix4 {
+    let mut res = Matrix4::identity();
+    res[(0, 3)] = x;
+    res[(1, 3)] = y;
+    res[(2, 3)] = z;
+    res
+}
+
+/// Scales each axis by x, y, z
+///
+/// ```
+/// use rtlib::matrix::transform::scaling;
+/// use rtlib::float::Float;
+/// use rtlib::tuple::Tuple;
+/// let t = scaling(Float(2.0), Float(3.0), Float(4.0));
+/// let p = Tuple::point_from_underlying(-4.0, 6.0, 8.0);
+/// assert_eq!(t * p, Tuple::point_from_underlying(-8.0, 18.0, 32.0));
+/// ```
+pub fn scaling(x: Float, y: Float, z: Float) -> Matrix4 {
+    let mut res = Matrix4::identity();
+    res[(0, 0)] = x;
+    res[(1, 1)] = y;
+    res[(2, 2)] = z;
+    res
+}
+
+/// Rotates by r radians around the x axis, following the left hand rule
+pub fn rotation_x(r: Float) -> Matrix4 {
+    let (sin, cos) = (r.sin(), r.cos());
+    let mut res = Matrix4::identity();
+    res[(1, 1)] = cos;
+    res[(1, 2)] = -sin;
+    res[(2, 1)] = sin;
+    res[(2, 2)] = cos;
+    res
+}
+
+/// Rotates by r radians around the y axis, following the left hand rule
+pub fn rotation_y(r: Float) -> Matrix4 {
+    let (sin, cos) = (r.sin(), r.cos());
+    let mut res = Matrix4::identity();
+    res[(0, 0)] = cos;
+    res[(0, 2)] = sin;
+    res[(2, 0)] = -sin;
+    res[(2, 2)] = cos;
+    res
+}
+
+/// Rotates by r radians around the z axis, following the left hand rule
+pub fn rotation_z(r: Float) -> Matrix4 {
+    let (sin, cos) = (r.sin(), r.cos());
+    let mut res = Matrix4::identity();
+    res[(0, 0)] = cos;
+    res[(0, 1)] = -sin;
+    res[(1, 0)] = sin;
+    res[(1, 1)] = cos;
+    res
+}
+
+/// Moves each component in proportion to the other two
+///
+/// xy moves x in proportion to y, xz moves x in proportion to z and so on.
+///
+/// ```
+/// use rtlib::matrix::transform::shearing;
+/// use rtlib::float::Float;
+/// use rtlib::tuple::Tuple;
+/// let zero = Float(0.0);
+/// let t = shearing(Float(1.0), zero, zero, zero, zero, zero);
+/// let p = Tuple::point_from_underlying(2.0, 3.0, 4.0);
+/// assert_eq!(t * p, Tuple::point_from_underlying(5.0, 3.0, 4.0));
+/// ```
+pub fn shearing(xy: Float, xz: Float, yx: Float, yz: Float, zx: Float, zy: Float) -> Matrix4 {
+    let mut res = Matrix4::identity();
+    res[(0, 1)] = xy;
+    res[(0, 2)] = xz;
+    res[(1, 0)] = yx;
+    res[(1, 2)] = yz;
+    res[(2, 0)] = zx;
+    res[(2, 1)] = zy;
+    res
+}
+
+/// Orients the world as seen by an eye at from looking towards to, with up roughly upwards
+///
+/// ```
+/// use rtlib::matrix::Matrix4;
+/// use rtlib::matrix::transform::view_transform;
+/// use rtlib::tuple::Tuple;
+/// let t = view_transform(
+///     Tuple::point_from_underlying(0.0, 0.0, 0.0),
+///     Tuple::point_from_underlying(0.0, 0.0, -1.0),
+///     Tuple::vector_from_underlying(0.0, 1.0, 0.0),
+/// );
+/// assert_eq!(t, Matrix4::identity());
+/// ```
+pub fn view_transform(from: Tuple, to: Tuple, up: Tuple) -> Matrix4 {
+    let forward = (to - from).normalize();
+    let left = forward % up.normalize();
+    let true_up = left % forward;
+    let orientation = Matrix4::new([
+        [left.get_x(), left.get_y(), left.get_z(), Float(0.0)],
+        [
+            true_up.get_x(),
+            true_up.get_y(),
+            true_up.get_z(),
+            Float(0.0),
+        ],
+        [
+            -forward.get_x(),
+            -forward.get_y(),
+            -forward.get_z(),
+            Float(0.0),
+        ],
+        [Float(0.0), Float(0.0), Float(0.0), Float(1.0)],
+    ]);
+    orientation * translation(-from.get_x(), -from.get_y(), -from.get_z())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
+
+    #[test]
+    fn view_transform() {
+        let origin = Tuple::point_from_underlying(0.0, 0.0, 0.0);
+        let up = Tuple::vector_from_underlying(0.0, 1.0, 0.0);
+        assert_eq!(
+            super::view_transform(origin, Tuple::point_from_underlying(0.0, 0.0, -1.0), up),
+            Matrix4::identity()
+        );
+        assert_eq!(
+            super::view_transform(origin, Tuple::point_from_underlying(0.0, 0.0, 1.0), up),
+            super::scaling(Float(-1.0), Float(1.0), Float(-1.0))
+        );
+        assert_eq!(
+            super::view_transform(Tuple::point_from_underlying(0.0, 0.0, 8.0), origin, up),
+            super::translation(Float(0.0), Float(0.0), Float(-8.0))
+        );
+        assert_eq!(
+            super::view_transform(
+                Tuple::point_from_underlying(1.0, 3.0, 2.0),
+                Tuple::point_from_underlying(4.0, -2.0, 8.0),
+                Tuple::vector_from_underlying(1.0, 1.0, 0.0)
+            ),
+            Matrix4::new_from_underlying([
+                [-0.50709, 0.50709, 0.67612, -2.36643],
+                [0.76772, 0.60609, 0.12122, -2.82843],
+                [-0.35857, 0.59761, -0.71714, 0.00000],
+                [0.00000, 0.00000, 0.00000, 1.00000],
+            ])
+        );
+    }
+
+    #[test]
+    fn translation() {
+        let t = super::translation(Float(5.0), Float(-3.0), Float(2.0));
+        let p = Tuple::point_from_underlying(-3.0, 4.0, 5.0);
+        assert_eq!(t * p, Tuple::point_from_underlying(2.0, 1.0, 7.0));
+        assert_eq!(
+            t.inverse().unwrap() * p,
+            Tuple::point_from_underlying(-8.0, 7.0, 3.0)
+        );
+        let moved = t * Tuple::point_from_underlying(0.0, 0.0, 0.0);
+        assert_eq!(
+            t.inverse().unwrap() * moved,
+            Tuple::point_from_underlying(0.0, 0.0, 0.0)
+        );
+        let v = Tuple::vector_from_underlying(-3.0, 4.0, 5.0);
+        assert_eq!(t * v, v);
+    }
+
+    #[test]
+    fn scaling() {
+        let t = super::scaling(Float(2.0), Float(3.0), Float(4.0));
+        assert_eq!(
+            t * Tuple::point_from_underlying(-4.0, 6.0, 8.0),
+            Tuple::point_from_underlying(-8.0, 18.0, 32.0)
+        );
+        let v = Tuple::vector_from_underlying(-4.0, 6.0, 8.0);
+        assert_eq!(t * v, Tuple::vector_from_underlying(-8.0, 18.0, 32.0));
+        assert_eq!(
+            t.inverse().unwrap() * v,
+            Tuple::vector_from_underlying(-2.0, 2.0, 2.0)
+        );
+        let reflection = super::scaling(Float(-1.0), Float(1.0), Float(1.0));
+        assert_eq!(
+            reflection * Tuple::point_from_underlying(2.0, 3.0, 4.0),
+            Tuple::point_from_underlying(-2.0, 3.0, 4.0)
+        );
+    }
+
+    #[test]
+    fn rotation_x() {
+        let p = Tuple::point_from_underlying(0.0, 1.0, 0.0);
+        let half_quarter = super::rotation_x(Float(FRAC_PI_4));
+        let full_quarter = super::rotation_x(Float(FRAC_PI_2));
+        let half = 2.0f64.sqrt() / 2.0;
+        assert_eq!(
+            half_quarter * p,
+            Tuple::point_from_underlying(0.0, half, half)
+        );
+        assert_eq!(
+            full_quarter * p,
+            Tuple::point_from_underlying(0.0, 0.0, 1.0)
+        );
+        assert_eq!(
+            half_quarter.inverse().unwrap() * p,
+            Tuple::point_from_underlying(0.0, half, -half)
+        );
+    }
+
+    #[test]
+    fn rotation_y() {
+        let p = Tuple::point_from_underlying(0.0, 0.0, 1.0);
+        let half = 2.0f64.sqrt() / 2.0;
+        assert_eq!(
+            super::rotation_y(Float(FRAC_PI_4)) * p,
+            Tuple::point_from_underlying(half, 0.0, half)
+        );
+        assert_eq!(
+            super::rotation_y(Float(FRAC_PI_2)) * p,
+            Tuple::point_from_underlying(1.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn rotation_z() {
+        let p = Tuple::point_from_underlying(0.0, 1.0, 0.0);
+        let half = 2.0f64.sqrt() / 2.0;
+        assert_eq!(
+            super::rotation_z(Float(FRAC_PI_4)) * p,
+            Tuple::point_from_underlying(-half, half, 0.0)
+        );
+        assert_eq!(
+            super::rotation_z(Float(FRAC_PI_2)) * p,
+            Tuple::point_from_underlying(-1.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn shearing() {
+        let p = Tuple::point_from_underlying(2.0, 3.0, 4.0);
+        let expected = [
+            (5.0, 3.0, 4.0),
+            (6.0, 3.0, 4.0),
+            (2.0, 5.0, 4.0),
+            (2.0, 7.0, 4.0),
+            (2.0, 3.0, 6.0),
+            (2.0, 3.0, 7.0),
+        ];
+        for (i, (x, y, z)) in expected.into_iter().enumerate() {
+            let mut factors = [Float(0.0); 6];
+            factors[i] = Float(1.0);
+            let [xy, xz, yx, yz, zx, zy] = factors;
+            assert_eq!(
+                super::shearing(xy, xz, yx, yz, zx, zy) * p,
+                Tuple::point_from_underlying(x, y, z)
+            );
+        }
+    }
+
+    #[test]
+    fn chained() {
+        let p = Tuple::point_from_underlying(1.0, 0.0, 1.0);
+        let a = super::rotation_x(Float(FRAC_PI_2));
+        let b = super::scaling(Float(5.0), Float(5.0), Float(5.0));
+        let c = super::translation(Float(10.0), Float(5.0), Float(7.0));
+        let p2 = a * p;
+        assert_eq!(p2, Tuple::point_from_underlying(1.0, -1.0, 0.0));
+        let p3 = b * p2;
+        assert_eq!(p3, Tuple::point_from_underlying(5.0, -5.0, 0.0));
+        let p4 = c * p3;
+        assert_eq!(p4, Tuple::point_from_underlying(15.0, 0.0, 7.0));
+        assert_eq!(c * b * a * p, p4);
+    }
+}
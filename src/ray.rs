@@ -0,0 +1,77 @@
+use crate::prelude::*;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Ray {
+    pub origin: Tuple,
+    pub direction: Tuple,
+}
+
+impl Ray {
+    pub fn new(origin: Tuple, direction: Tuple) -> Self {
+        Self { origin, direction }
+    }
+
+    /// Yields the point at distance t along the ray
+    ///
+    /// ```
+    /// use rtlib::ray::Ray;
+    /// use rtlib::float::Float;
+    /// use rtlib::tuple::Tuple;
+    /// let r = Ray::new(
+    ///     Tuple::point_from_underlying(2.0, 3.0, 4.0),
+    ///     Tuple::vector_from_underlying(1.0, 0.0, 0.0),
+    /// );
+    /// assert_eq!(r.position(Float(1.0)), Tuple::point_from_underlying(3.0, 3.0, 4.0));
+    /// ```
+    pub fn position(&self, t: Float) -> Tuple {
+        self.origin + self.direction * t
+    }
+
+    /// Applies m to origin and direction
+    pub fn transform(&self, m: &Matrix4) -> Ray {
+        Ray::new(*m * self.origin, *m * self.direction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::transform::{scaling, translation};
+
+    #[test]
+    fn position() {
+        let r = Ray::new(
+            Tuple::point_from_underlying(2.0, 3.0, 4.0),
+            Tuple::vector_from_underlying(1.0, 0.0, 0.0),
+        );
+        for (t, x) in [(0.0, 2.0), (1.0, 3.0), (-1.0, 1.0), (2.5, 4.5)] {
+            assert_eq!(
+                r.position(Float(t)),
+                Tuple::point_from_underlying(x, 3.0, 4.0)
+            );
+        }
+    }
+
+    #[test]
+    fn transform() {
+        let r = Ray::new(
+            Tuple::point_from_underlying(1.0, 2.0, 3.0),
+            Tuple::vector_from_underlying(0.0, 1.0, 0.0),
+        );
+        let translated = r.transform(&translation(Float(3.0), Float(4.0), Float(5.0)));
+        assert_eq!(
+            translated.origin,
+            Tuple::point_from_underlying(4.0, 6.0, 8.0)
+        );
+        assert_eq!(
+            translated.direction,
+            Tuple::vector_from_underlying(0.0, 1.0, 0.0)
+        );
+        let scaled = r.transform(&scaling(Float(2.0), Float(3.0), Float(4.0)));
+        assert_eq!(scaled.origin, Tuple::point_from_underlying(2.0, 6.0, 12.0));
+        assert_eq!(
+            scaled.direction,
+            Tuple::vector_from_underlying(0.0, 3.0, 0.0)
+        );
+    }
+}
@@ -0,0 +1,170 @@
+use crate::prelude::*;
+
+/// Unit sphere at the origin of object space, placed in the world by transform
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Sphere {
+    pub transform: Matrix4,
+}
+
+impl Default for Sphere {
+    fn default() -> Self {
+        Self::new(Matrix4::identity())
+    }
+}
+
+impl Sphere {
+    pub fn new(transform: Matrix4) -> Self {
+        Self { transform }
+    }
+
+    /// Yields the t values at which ray enters and leaves the sphere
+    ///
+    /// A tangent ray yields its single t twice. A sphere with a singular transform is never
+    /// hit.
+    ///
+    /// ```
+    /// use rtlib::float::Float;
+    /// use rtlib::ray::Ray;
+    /// use rtlib::shapes::sphere::Sphere;
+    /// use rtlib::tuple::Tuple;
+    /// let r = Ray::new(
+    ///     Tuple::point_from_underlying(0.0, 0.0, -5.0),
+    ///     Tuple::vector_from_underlying(0.0, 0.0, 1.0),
+    /// );
+    /// assert_eq!(Sphere::default().intersect(&r), [Float(4.0), Float(6.0)]);
+    /// ```
+    pub fn intersect(&self, ray: &Ray) -> Vec<Float> {
+        let Some(inverse) = self.transform.inverse() else {
+            return Vec::new();
+        };
+        let ray = ray.transform(&inverse);
+        let sphere_to_ray = ray.origin - Tuple::point_from_underlying(0.0, 0.0, 0.0);
+        let a = ray.direction * ray.direction;
+        let b = Float(2.0) * (ray.direction * sphere_to_ray);
+        let c = sphere_to_ray * sphere_to_ray - Float(1.0);
+        let discriminant = b * b - Float(4.0) * a * c;
+        if discriminant.0 < 0.0 {
+            return Vec::new();
+        }
+        let root = discriminant.sqrt();
+        vec![(-b - root) / (a * 2.0), (-b + root) / (a * 2.0)]
+    }
+
+    /// Like intersect, but pairing each t with the sphere
+    pub fn intersections(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        self.intersect(ray)
+            .into_iter()
+            .map(|t| Intersection::new(t, self))
+            .collect()
+    }
+
+    /// Yields the unit surface normal at world_point, assumed to lie on the sphere
+    ///
+    /// Panics for a singular transform, as such a sphere has no surface to be on.
+    ///
+    /// ```
+    /// use rtlib::shapes::sphere::Sphere;
+    /// use rtlib::tuple::Tuple;
+    /// assert_eq!(
+    ///     Sphere::default().normal_at(Tuple::point_from_underlying(1.0, 0.0, 0.0)),
+    ///     Tuple::vector_from_underlying(1.0, 0.0, 0.0)
+    /// );
+    /// ```
+    pub fn normal_at(&self, world_point: Tuple) -> Tuple {
+        let inverse = self
+            .transform
+            .inverse()
+            .expect("sphere transform must be invertible");
+        let object_point = inverse * world_point;
+        let object_normal = object_point - Tuple::point_from_underlying(0.0, 0.0, 0.0);
+        let mut world_normal = inverse.transpose() * object_normal;
+        *world_normal.get_mut_w() = Float(0.0);
+        world_normal.normalize()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::transform::{rotation_z, scaling, translation};
+    use std::f64::consts::{FRAC_1_SQRT_2, PI};
+
+    fn ray_along_z(x: UnderlyingFloat, y: UnderlyingFloat, z: UnderlyingFloat) -> Ray {
+        Ray::new(
+            Tuple::point_from_underlying(x, y, z),
+            Tuple::vector_from_underlying(0.0, 0.0, 1.0),
+        )
+    }
+
+    #[test]
+    fn intersect() {
+        let s = Sphere::default();
+        assert_eq!(
+            s.intersect(&ray_along_z(0.0, 0.0, -5.0)),
+            [Float(4.0), Float(6.0)]
+        );
+        assert_eq!(
+            s.intersect(&ray_along_z(0.0, 1.0, -5.0)),
+            [Float(5.0), Float(5.0)]
+        );
+        assert!(s.intersect(&ray_along_z(0.0, 2.0, -5.0)).is_empty());
+        assert_eq!(
+            s.intersect(&ray_along_z(0.0, 0.0, 0.0)),
+            [Float(-1.0), Float(1.0)]
+        );
+        assert_eq!(
+            s.intersect(&ray_along_z(0.0, 0.0, 5.0)),
+            [Float(-6.0), Float(-4.0)]
+        );
+    }
+
+    #[test]
+    fn default_transform() {
+        assert_eq!(Sphere::default().transform, Matrix4::identity());
+    }
+
+    #[test]
+    fn intersect_transformed() {
+        let r = ray_along_z(0.0, 0.0, -5.0);
+        let scaled = Sphere::new(scaling(Float(2.0), Float(2.0), Float(2.0)));
+        assert_eq!(scaled.intersect(&r), [Float(3.0), Float(7.0)]);
+        let translated = Sphere::new(translation(Float(5.0), Float(0.0), Float(0.0)));
+        assert!(translated.intersect(&r).is_empty());
+        let singular = Sphere::new(scaling(Float(0.0), Float(1.0), Float(1.0)));
+        assert!(singular.intersect(&r).is_empty());
+    }
+
+    #[test]
+    fn normal_at() {
+        let s = Sphere::default();
+        for (x, y, z) in [(1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (0.0, 0.0, 1.0)] {
+            assert_eq!(
+                s.normal_at(Tuple::point_from_underlying(x, y, z)),
+                Tuple::vector_from_underlying(x, y, z)
+            );
+        }
+        let third = 3.0f64.sqrt() / 3.0;
+        let n = s.normal_at(Tuple::point_from_underlying(third, third, third));
+        assert_eq!(n, Tuple::vector_from_underlying(third, third, third));
+        assert_eq!(n, n.normalize());
+    }
+
+    #[test]
+    fn normal_at_transformed() {
+        let translated = Sphere::new(translation(Float(0.0), Float(1.0), Float(0.0)));
+        assert_eq!(
+            translated.normal_at(Tuple::point_from_underlying(
+                0.0,
+                1.0 + FRAC_1_SQRT_2,
+                -FRAC_1_SQRT_2
+            )),
+            Tuple::vector_from_underlying(0.0, FRAC_1_SQRT_2, -FRAC_1_SQRT_2)
+        );
+        let transformed =
+            Sphere::new(scaling(Float(1.0), Float(0.5), Float(1.0)) * rotation_z(Float(PI / 5.0)));
+        let half = 2.0f64.sqrt() / 2.0;
+        let n = transformed.normal_at(Tuple::point_from_underlying(0.0, half, -half));
+        assert_eq!(n, Tuple::vector_from_underlying(0.0, 0.97014, -0.24254));
+        assert_eq!(n, n.normalize());
+    }
+}
@@ -7,6 +7,34 @@ pub struct Tuple {
     pub elements: [Float; 4],
 }
 
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Axis3 {
+    X,
+    Y,
+    Z,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TupleOpError {
+    PointPlusPoint,
+    InvalidW(Float),
+    NotAVector(Tuple),
+}
+
+impl std::fmt::Display for TupleOpError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TupleOpError::PointPlusPoint => write!(f, "cannot add two points"),
+            TupleOpError::InvalidW(w) => {
+                write!(f, "result has w = {} which is neither point nor vector", w)
+            }
+            TupleOpError::NotAVector(t) => write!(f, "expected a vector, got {}", t),
+        }
+    }
+}
+
+impl std::error::Error for TupleOpError {}
+
 impl std::fmt::Display for Tuple {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         #[derive(PartialEq)]
@@ -204,6 +232,65 @@ impl Tuple {
         &mut self.get_mut_w().0
     }
 
+    /// Gets the component along axis
+    ///
+    /// ```
+    /// use rtlib::tuple::{Axis3, Tuple};
+    /// use rtlib::float::Float;
+    /// let mut a = Tuple::point_from_underlying(1.0, 2.0, 3.0);
+    /// assert_eq!(a.get_axis(Axis3::Y), 2.0);
+    /// a.set_axis(Axis3::Y, Float(-5.0));
+    /// assert_eq!(a, Tuple::point_from_underlying(1.0, -5.0, 3.0));
+    /// ```
+    pub fn get_axis(self, axis: Axis3) -> Float {
+        match axis {
+            Axis3::X => self.get_x(),
+            Axis3::Y => self.get_y(),
+            Axis3::Z => self.get_z(),
+        }
+    }
+
+    /// Sets the component along axis
+    pub fn set_axis(&mut self, axis: Axis3, v: Float) {
+        *match axis {
+            Axis3::X => self.get_mut_x(),
+            Axis3::Y => self.get_mut_y(),
+            Axis3::Z => self.get_mut_z(),
+        } = v;
+    }
+
+    /// Yields the underlying x, y, z, w
+    pub fn to_underlying_array(&self) -> [UnderlyingFloat; 4] {
+        self.elements.map(|f| f.0)
+    }
+
+    /// Dot product of two tuples, same as `*`
+    ///
+    /// ```
+    /// use rtlib::tuple::Tuple;
+    /// let a = Tuple::vector_from_underlying(1.0, 2.0, 3.0);
+    /// let b = Tuple::vector_from_underlying(2.0, 3.0, 4.0);
+    /// assert_eq!(a.dot(b), 20.0);
+    /// assert_eq!(a.dot(b), a * b);
+    /// ```
+    pub fn dot(self, other: Tuple) -> Float {
+        self * other
+    }
+
+    /// Cross product of two tuples, same as `%`
+    ///
+    /// ```
+    /// use rtlib::tuple::Tuple;
+    /// let a = Tuple::vector_from_underlying(1.0, 2.0, 3.0);
+    /// let b = Tuple::vector_from_underlying(2.0, 3.0, 4.0);
+    /// assert_eq!(a.cross(b), Tuple::vector_from_underlying(-1.0, 2.0, -1.0));
+    /// assert_eq!(b.cross(a), Tuple::vector_from_underlying(1.0, -2.0, 1.0));
+    /// assert_eq!(a.cross(b), a % b);
+    /// ```
+    pub fn cross(self, other: Tuple) -> Tuple {
+        self % other
+    }
+
     /// Calculates magnitude of Tuple
     ///
     /// ```
@@ -215,8 +302,69 @@ impl Tuple {
         self.elements.iter().map(|f| *f * *f).sum::<Float>().sqrt()
     }
 
+    /// Calculates magnitude of Tuple without intermediate overflow or underflow
+    ///
+    /// Components are scaled by the largest absolute component before squaring.
+    pub fn magnitude_robust(self) -> Float {
+        let scale = self
+            .elements
+            .iter()
+            .fold(0.0, |max: UnderlyingFloat, f| max.max(f.0.abs()));
+        if scale == 0.0 || !scale.is_finite() {
+            return Float(scale);
+        }
+        (self / scale).magnitude() * Float(scale)
+    }
+
+    /// Calculates the distance between two tuples
+    ///
+    /// Intended for points, but it is the magnitude of the componentwise difference, so a
+    /// differing w contributes as well.
+    ///
+    /// ```
+    /// use rtlib::tuple::Tuple;
+    /// let a = Tuple::point_from_underlying(1.0, 0.0, 0.0);
+    /// let b = Tuple::point_from_underlying(4.0, 0.0, 0.0);
+    /// assert_eq!(a.distance(b), 3.0);
+    /// ```
+    pub fn distance(self, other: Tuple) -> Float {
+        self.distance_squared(other).sqrt()
+    }
+
+    /// Calculates the squared distance between two tuples, avoiding the square root
+    ///
+    /// ```
+    /// use rtlib::tuple::Tuple;
+    /// let a = Tuple::point_from_underlying(1.0, 0.0, 0.0);
+    /// let b = Tuple::point_from_underlying(4.0, 0.0, 0.0);
+    /// assert_eq!(a.distance_squared(b), 9.0);
+    /// ```
+    pub fn distance_squared(self, other: Tuple) -> Float {
+        let difference = self - other;
+        difference * difference
+    }
+
+    /// Checks whether the magnitude is within EPSILON of 1
+    ///
+    /// ```
+    /// use rtlib::tuple::Tuple;
+    /// assert!(Tuple::vector_from_underlying(1.0, 2.0, 3.0).normalize().is_unit());
+    /// assert!(!Tuple::vector_from_underlying(2.0, 0.0, 0.0).is_unit());
+    /// ```
+    pub fn is_unit(&self) -> bool {
+        self.magnitude() == 1.0
+    }
+
+    /// Passes self through, panicking in debug builds if it is not of unit length
+    pub fn expect_unit(self) -> Tuple {
+        debug_assert!(self.is_unit(), "expected unit length, got {}", self);
+        self
+    }
+
     /// Yields the normalization of a Tuple
     ///
+    /// A zero length Tuple yields NaN components; use try_normalize where that can happen.
+    ///
     /// ```
     /// use rtlib::tuple::Tuple;
     /// use rtlib::float::Float;
@@ -227,6 +375,331 @@ impl Tuple {
         let mag = self.magnitude();
         self / mag
     }
+
+    /// Yields the normalization of a Tuple, or None if its magnitude is within EPSILON of 0
+    ///
+    /// ```
+    /// use rtlib::tuple::Tuple;
+    /// assert_eq!(Tuple::zero().try_normalize(), None);
+    /// assert_eq!(
+    ///     Tuple::vector_from_underlying(0.0, 3.0, 0.0).try_normalize(),
+    ///     Some(Tuple::vector_from_underlying(0.0, 1.0, 0.0))
+    /// );
+    /// ```
+    pub fn try_normalize(self) -> Option<Self> {
+        let mag = self.magnitude();
+        if mag == 0.0 {
+            return None;
+        }
+        Some(self / mag)
+    }
+
+    /// Spherically interpolates between two direction vectors
+    ///
+    /// Both inputs are normalized first. Nearly parallel vectors fall back to a normalized
+    /// linear interpolation. The result is undefined for opposite directions.
+    ///
+    /// ```
+    /// use rtlib::tuple::Tuple;
+    /// use rtlib::float::Float;
+    /// let a = Tuple::vector_from_underlying(1.0, 0.0, 0.0);
+    /// let b = Tuple::vector_from_underlying(0.0, 1.0, 0.0);
+    /// let half = 0.5f64.sqrt();
+    /// assert_eq!(a.slerp(b, Float(0.5)), Tuple::vector_from_underlying(half, half, 0.0));
+    /// assert_eq!(a.slerp(b, Float(0.5)).magnitude(), 1.0);
+    /// assert_eq!(a.slerp(b * 3.0, Float(1.0)), b);
+    /// ```
+    pub fn slerp(self, other: Tuple, t: Float) -> Tuple {
+        let a = self.normalize();
+        let b = other.normalize();
+        let dot = Float((a * b).0.clamp(-1.0, 1.0));
+        if dot.0 > 1.0 - EPSILON {
+            return (a + (b - a) * t).normalize();
+        }
+        let theta = dot.acos();
+        let sin_theta = theta.sin();
+        a * (((Float(1.0) - t) * theta).sin() / sin_theta) + b * ((t * theta).sin() / sin_theta)
+    }
+
+    /// Projects self onto other
+    ///
+    /// Both are treated as vectors and other must be non-zero.
+    ///
+    /// ```
+    /// use rtlib::tuple::Tuple;
+    /// let v = Tuple::vector_from_underlying(2.0, 2.0, 0.0);
+    /// let x = Tuple::vector_from_underlying(1.0, 0.0, 0.0);
+    /// assert_eq!(v.project_onto(x), Tuple::vector_from_underlying(2.0, 0.0, 0.0));
+    /// ```
+    pub fn project_onto(self, other: Tuple) -> Tuple {
+        other * (self * other / (other * other))
+    }
+
+    /// Removes the component of self along other, leaving the perpendicular part
+    ///
+    /// Both are treated as vectors and other must be non-zero.
+    ///
+    /// ```
+    /// use rtlib::tuple::Tuple;
+    /// let v = Tuple::vector_from_underlying(2.0, 2.0, 0.0);
+    /// let x = Tuple::vector_from_underlying(1.0, 0.0, 0.0);
+    /// assert_eq!(v.reject_from(x), Tuple::vector_from_underlying(0.0, 2.0, 0.0));
+    /// ```
+    pub fn reject_from(self, other: Tuple) -> Tuple {
+        self - self.project_onto(other)
+    }
+
+    /// Calculates the angle between two vectors in radians
+    ///
+    /// Yields 0 if either magnitude is zero.
+    ///
+    /// ```
+    /// use rtlib::tuple::Tuple;
+    /// let a = Tuple::vector_from_underlying(1.0, 0.0, 0.0);
+    /// let b = Tuple::vector_from_underlying(1.0, 1.0, 0.0);
+    /// assert_eq!(a.angle_between(b), std::f64::consts::FRAC_PI_4);
+    /// ```
+    pub fn angle_between(self, other: Tuple) -> Float {
+        let lengths = self.magnitude() * other.magnitude();
+        if lengths.0 == 0.0 {
+            return Float(0.0);
+        }
+        (self * other / lengths)
+            .clamp(Float(-1.0), Float(1.0))
+            .acos()
+    }
+
+    /// Reflects the Tuple across the x axis by negating y
+    ///
+    /// ```
+    /// use rtlib::tuple::Tuple;
+    /// assert_eq!(
+    ///   Tuple::point_from_underlying(1.0, 2.0, 3.0).flip_y(),
+    ///   Tuple::point_from_underlying(1.0, -2.0, 3.0)
+    /// );
+    /// ```
+    pub fn flip_y(self) -> Tuple {
+        Self::new(self.get_x(), -self.get_y(), self.get_z(), self.get_w())
+    }
+
+    /// Adds two Tuples, rejecting the addition of two points
+    pub fn checked_add(self, other: Tuple) -> Result<Tuple, TupleOpError> {
+        if self.is_point() && other.is_point() {
+            return Err(TupleOpError::PointPlusPoint);
+        }
+        Ok(self + other)
+    }
+
+    /// Subs two Tuples, rejecting results that are neither point nor vector
+    pub fn checked_sub(self, other: Tuple) -> Result<Tuple, TupleOpError> {
+        let res = self - other;
+        if !res.is_point() && !res.is_vector() {
+            return Err(TupleOpError::InvalidW(res.get_w()));
+        }
+        Ok(res)
+    }
+
+    /// Cross product that requires both operands to be vectors
+    pub fn cross_checked(self, other: Tuple) -> Result<Tuple, TupleOpError> {
+        if let Some(t) = [self, other].into_iter().find(|t| !t.is_vector()) {
+            return Err(TupleOpError::NotAVector(t));
+        }
+        Ok(self % other)
+    }
+
+    /// Finds the point on segment a→b closest to p
+    ///
+    /// A zero length segment yields a.
+    ///
+    /// ```
+    /// use rtlib::tuple::Tuple;
+    /// assert_eq!(
+    ///     Tuple::closest_point_on_segment(
+    ///         Tuple::point_from_underlying(0.0, 1.0, 0.0),
+    ///         Tuple::point_from_underlying(-1.0, 0.0, 0.0),
+    ///         Tuple::point_from_underlying(1.0, 0.0, 0.0),
+    ///     ),
+    ///     Tuple::point_from_underlying(0.0, 0.0, 0.0)
+    /// );
+    /// ```
+    pub fn closest_point_on_segment(p: Tuple, a: Tuple, b: Tuple) -> Tuple {
+        let ab = b - a;
+        let length_squared = ab * ab;
+        if length_squared.0 == 0.0 {
+            return a;
+        }
+        let t = ((p - a) * ab / length_squared).0.clamp(0.0, 1.0);
+        a + ab * t
+    }
+
+    /// Computes the orthonormal right, true up and forward vectors of a camera at eye
+    /// looking at center
+    ///
+    /// up only needs to point roughly upwards. If it is zero or parallel to the view
+    /// direction another axis is substituted, so a basis is always returned as long as eye
+    /// and center differ.
+    ///
+    /// ```
+    /// use rtlib::tuple::Tuple;
+    /// let (right, up, forward) = Tuple::look_at_basis(
+    ///     Tuple::point_from_underlying(0.0, 0.0, 0.0),
+    ///     Tuple::point_from_underlying(0.0, 0.0, 1.0),
+    ///     Tuple::vector_from_underlying(0.0, 1.0, 0.0),
+    /// );
+    /// assert_eq!(right, Tuple::vector_from_underlying(-1.0, 0.0, 0.0));
+    /// assert_eq!(up, Tuple::vector_from_underlying(0.0, 1.0, 0.0));
+    /// assert_eq!(forward, Tuple::vector_from_underlying(0.0, 0.0, 1.0));
+    /// ```
+    pub fn look_at_basis(eye: Tuple, center: Tuple, up: Tuple) -> (Tuple, Tuple, Tuple) {
+        let forward = (center - eye).normalize();
+        let right = (forward % up).try_normalize().unwrap_or_else(|| {
+            let fallback = if forward.get_y().abs() < 0.9 {
+                Tuple::vector_from_underlying(0.0, 1.0, 0.0)
+            } else {
+                Tuple::vector_from_underlying(0.0, 0.0, -1.0)
+            };
+            (forward % fallback).normalize()
+        });
+        (right, right % forward, forward)
+    }
+
+    /// Generates n roughly evenly distributed unit vectors along a Fibonacci spiral
+    ///
+    /// ```
+    /// use rtlib::tuple::Tuple;
+    /// let directions = Tuple::fibonacci_sphere(16);
+    /// assert_eq!(directions.len(), 16);
+    /// assert!(directions.iter().all(|d| d.is_vector() && d.is_unit()));
+    /// ```
+    pub fn fibonacci_sphere(n: usize) -> Vec<Tuple> {
+        let golden_angle = std::f64::consts::PI * (3.0 - UnderlyingFloat::sqrt(5.0));
+        (0..n)
+            .map(|i| {
+                let y = 1.0 - 2.0 * (i as UnderlyingFloat + 0.5) / n as UnderlyingFloat;
+                let radius = (1.0 - y * y).sqrt();
+                let theta = golden_angle * i as UnderlyingFloat;
+                Tuple::vector_from_underlying(theta.cos() * radius, y, theta.sin() * radius)
+            })
+            .collect()
+    }
+
+    /// Computes the plane through three points as unit normal and signed distance
+    ///
+    /// The plane contains every point p with normal · p = distance. The normal follows the
+    /// right hand rule for a, b, c. Returns None if the points are collinear.
+    ///
+    /// ```
+    /// use rtlib::tuple::Tuple;
+    /// use rtlib::float::Float;
+    /// let (normal, distance) = Tuple::plane_from_points(
+    ///     Tuple::point_from_underlying(0.0, 0.0, 0.0),
+    ///     Tuple::point_from_underlying(1.0, 0.0, 0.0),
+    ///     Tuple::point_from_underlying(0.0, 1.0, 0.0),
+    /// ).unwrap();
+    /// assert_eq!(normal, Tuple::vector_from_underlying(0.0, 0.0, 1.0));
+    /// assert_eq!(distance, 0.0);
+    /// ```
+    pub fn plane_from_points(a: Tuple, b: Tuple, c: Tuple) -> Option<(Tuple, Float)> {
+        let cross = (b - a) % (c - a);
+        if cross.magnitude().0 == 0.0 {
+            return None;
+        }
+        let normal = cross.normalize();
+        Some((normal, normal * a.vector_part()))
+    }
+
+    fn vector_part(self) -> Tuple {
+        Self::vector(self.get_x(), self.get_y(), self.get_z())
+    }
+
+    /// Flips the normal if needed so it opposes against
+    ///
+    /// ```
+    /// use rtlib::tuple::Tuple;
+    /// let normal = Tuple::vector_from_underlying(0.0, 0.0, 1.0);
+    /// let ray = Tuple::vector_from_underlying(0.0, 1.0, 1.0);
+    /// assert_eq!(normal.face_forward(ray), Tuple::vector_from_underlying(0.0, 0.0, -1.0));
+    /// assert_eq!(normal.face_forward(-ray), normal);
+    /// ```
+    pub fn face_forward(self, against: Tuple) -> Tuple {
+        if (self * against).0 < 0.0 {
+            self
+        } else {
+            -self
+        }
+    }
+
+    /// Reflects self off normal
+    ///
+    /// Both are expected to be vectors (checked in debug builds); a point would pick up the
+    /// normal scaled by its w component.
+    ///
+    /// ```
+    /// use rtlib::tuple::Tuple;
+    /// let v = Tuple::vector_from_underlying(1.0, -1.0, 0.0);
+    /// let n = Tuple::vector_from_underlying(0.0, 1.0, 0.0);
+    /// assert_eq!(v.reflect(n), Tuple::vector_from_underlying(1.0, 1.0, 0.0));
+    /// ```
+    pub fn reflect(self, normal: Tuple) -> Tuple {
+        debug_assert!(self.is_vector(), "reflect requires a vector");
+        debug_assert!(normal.is_vector(), "reflect requires a vector normal");
+        self - normal * 2.0 * (self * normal)
+    }
+
+    /// Reflects self off normal, perturbed within a cone around the mirror direction
+    ///
+    /// The cone half-angle is roughness times 90 degrees, with roughness clamped to [0, 1].
+    /// The two samples in [0, 1] select a direction in the cone, so a zero roughness yields
+    /// reflect. The magnitude of the reflection is preserved.
+    pub fn reflect_jittered(
+        self,
+        normal: Tuple,
+        roughness: Float,
+        rng_sample: (Float, Float),
+    ) -> Tuple {
+        let reflected = self.reflect(normal);
+        let roughness = roughness.clamp(Float(0.0), Float(1.0));
+        let length = reflected.magnitude();
+        if roughness == 0.0 || length == 0.0 {
+            return reflected;
+        }
+        let d = reflected / length;
+        let helper = if d.get_x().abs() < 0.9 {
+            Tuple::vector_from_underlying(1.0, 0.0, 0.0)
+        } else {
+            Tuple::vector_from_underlying(0.0, 1.0, 0.0)
+        };
+        let u = helper.cross(d).normalize();
+        let v = d.cross(u);
+        let cos_max = (roughness * std::f64::consts::FRAC_PI_2).cos();
+        let cos_theta = Float(1.0) - rng_sample.0 * (Float(1.0) - cos_max);
+        let sin_theta = (Float(1.0) - cos_theta * cos_theta).max(Float(0.0)).sqrt();
+        let phi = rng_sample.1 * (2.0 * std::f64::consts::PI);
+        (u * (sin_theta * phi.cos()) + v * (sin_theta * phi.sin()) + d * cos_theta) * length
+    }
+
+    /// Reflects every vector in place off normal
+    ///
+    /// ```
+    /// use rtlib::tuple::Tuple;
+    /// let mut vectors = [Tuple::vector_from_underlying(1.0, -1.0, 0.0)];
+    /// Tuple::reflect_all(&mut vectors, Tuple::vector_from_underlying(0.0, 1.0, 0.0));
+    /// assert_eq!(vectors, [Tuple::vector_from_underlying(1.0, 1.0, 0.0)]);
+    /// ```
+    pub fn reflect_all(vectors: &mut [Tuple], normal: Tuple) {
+        vectors.iter_mut().for_each(|v| *v = v.reflect(normal));
+    }
+
+    /// Snaps w to exactly 0 or 1 if it is within EPSILON, otherwise leaves it
+    pub fn canonicalize_w(self) -> Tuple {
+        let mut res = self;
+        if res.is_point() {
+            *res.get_mut_w() = Float(1.0);
+        } else if res.is_vector() {
+            *res.get_mut_w() = Float(0.0);
+        }
+        res
+    }
 }
 
 impl std::ops::AddAssign for Tuple {
@@ -538,6 +1011,91 @@ impl std::ops::Rem for Tuple {
     }
 }
 
+impl std::ops::Index<usize> for Tuple {
+    type Output = Float;
+    /// Component by position, with x, y, z, w at 0 to 3
+    ///
+    /// ```
+    /// use rtlib::tuple::Tuple;
+    /// let a = Tuple::point_from_underlying(1.0, 2.0, 3.0);
+    /// assert_eq!(a[2], 3.0);
+    /// assert_eq!(a[3], 1.0);
+    /// ```
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.elements[index]
+    }
+}
+
+impl std::ops::IndexMut<usize> for Tuple {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.elements[index]
+    }
+}
+
+impl IntoIterator for Tuple {
+    type Item = Float;
+    type IntoIter = std::array::IntoIter<Float, 4>;
+    /// Iterates over x, y, z, w
+    ///
+    /// ```
+    /// use rtlib::tuple::Tuple;
+    /// use rtlib::float::Float;
+    /// let a = Tuple::point_from_underlying(1.0, 2.0, 3.0);
+    /// assert_eq!(a.into_iter().sum::<Float>(), 7.0);
+    /// ```
+    fn into_iter(self) -> Self::IntoIter {
+        self.elements.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Tuple {
+    type Item = &'a Float;
+    type IntoIter = std::slice::Iter<'a, Float>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.elements.iter()
+    }
+}
+
+impl From<[Float; 4]> for Tuple {
+    /// Creates a tuple from x, y, z, w
+    ///
+    /// ```
+    /// use rtlib::tuple::Tuple;
+    /// use rtlib::float::Float;
+    /// let a = Tuple::from([Float(1.0), Float(2.0), Float(3.0), Float(1.0)]);
+    /// assert_eq!(a, Tuple::point_from_underlying(1.0, 2.0, 3.0));
+    /// ```
+    fn from(elements: [Float; 4]) -> Self {
+        Self { elements }
+    }
+}
+
+impl From<[UnderlyingFloat; 4]> for Tuple {
+    /// Creates a tuple from underlying x, y, z, w
+    ///
+    /// ```
+    /// use rtlib::tuple::Tuple;
+    /// let a = Tuple::from([1.0, 2.0, 3.0, 0.0]);
+    /// assert_eq!(a, Tuple::vector_from_underlying(1.0, 2.0, 3.0));
+    /// ```
+    fn from(elements: [UnderlyingFloat; 4]) -> Self {
+        Self {
+            elements: elements.map(Float),
+        }
+    }
+}
+
+impl From<Tuple> for [UnderlyingFloat; 4] {
+    /// ```
+    /// use rtlib::tuple::Tuple;
+    /// let a: [f64; 4] = Tuple::vector_from_underlying(1.0, 2.0, 3.0).into();
+    /// assert_eq!(a, [1.0, 2.0, 3.0, 0.0]);
+    /// ```
+    fn from(tuple: Tuple) -> Self {
+        tuple.to_underlying_array()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -573,6 +1131,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn robust_magnitudes() {
+        let huge = Tuple::vector_from_underlying(3e200, 4e200, 0.0);
+        assert!(huge.magnitude().0.is_infinite());
+        assert_eq!(huge.magnitude_robust() / Float(1e200), 5.0);
+
+        let tiny = Tuple::vector_from_underlying(3e-200, 4e-200, 0.0);
+        assert_eq!(tiny.magnitude().0, 0.0);
+        assert_eq!(tiny.magnitude_robust() / Float(1e-200), 5.0);
+
+        assert_eq!(
+            Tuple::vector_from_underlying(1.0, 2.0, 3.0).magnitude_robust(),
+            14.0f64.sqrt()
+        );
+        assert_eq!(Tuple::zero().magnitude_robust(), 0.0);
+    }
+
+    #[test]
+    fn expect_unit_passes_unit_vectors() {
+        let v = Tuple::vector_from_underlying(0.0, 3.0, 4.0).normalize();
+        assert_eq!(v.expect_unit(), v);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "expected unit length")]
+    fn expect_unit_panics_otherwise() {
+        Tuple::vector_from_underlying(2.0, 0.0, 0.0).expect_unit();
+    }
+
     #[test]
     fn normalizations() {
         assert_eq!(
@@ -611,6 +1199,342 @@ mod tests {
         }
     }
 
+    #[test]
+    fn canonicalize_w() {
+        let near_point = Tuple::new_from_underlying(1.0, 2.0, 3.0, 0.9999998);
+        assert_eq!(
+            near_point.canonicalize_w().get_underlying_w().to_bits(),
+            1.0f64.to_bits()
+        );
+        let near_vector = Tuple::new_from_underlying(1.0, 2.0, 3.0, -0.0000002);
+        assert_eq!(
+            near_vector.canonicalize_w().get_underlying_w().to_bits(),
+            0.0f64.to_bits()
+        );
+        let neither = Tuple::new_from_underlying(1.0, 2.0, 3.0, 0.5);
+        assert_eq!(
+            neither.canonicalize_w().get_underlying_w().to_bits(),
+            0.5f64.to_bits()
+        );
+        assert_eq!(
+            near_point.canonicalize_w().elements[..3],
+            near_point.elements[..3]
+        );
+    }
+
+    #[test]
+    fn checked_add() {
+        let p = Tuple::point_from_underlying(1.0, 2.0, 3.0);
+        let v = Tuple::vector_from_underlying(4.0, 5.0, 6.0);
+        assert_eq!(p.checked_add(p), Err(TupleOpError::PointPlusPoint));
+        assert_eq!(
+            p.checked_add(v),
+            Ok(Tuple::point_from_underlying(5.0, 7.0, 9.0))
+        );
+        assert_eq!(
+            v.checked_add(v),
+            Ok(Tuple::vector_from_underlying(8.0, 10.0, 12.0))
+        );
+    }
+
+    #[test]
+    fn checked_sub() {
+        let p = Tuple::point_from_underlying(1.0, 2.0, 3.0);
+        let v = Tuple::vector_from_underlying(4.0, 5.0, 6.0);
+        assert_eq!(
+            p.checked_sub(p),
+            Ok(Tuple::vector_from_underlying(0.0, 0.0, 0.0))
+        );
+        assert_eq!(
+            p.checked_sub(v),
+            Ok(Tuple::point_from_underlying(-3.0, -3.0, -3.0))
+        );
+        assert_eq!(v.checked_sub(p), Err(TupleOpError::InvalidW(Float(-1.0))));
+    }
+
+    #[test]
+    fn cross_checked() {
+        let a = Tuple::vector_from_underlying(1.0, 2.0, 3.0);
+        let b = Tuple::vector_from_underlying(2.0, 3.0, 4.0);
+        let p = Tuple::point_from_underlying(2.0, 3.0, 4.0);
+        assert_eq!(
+            a.cross_checked(b),
+            Ok(Tuple::vector_from_underlying(-1.0, 2.0, -1.0))
+        );
+        assert_eq!(a.cross_checked(p), Err(TupleOpError::NotAVector(p)));
+        assert_eq!(p.cross_checked(a), Err(TupleOpError::NotAVector(p)));
+        assert_eq!(a % p, a % b);
+    }
+
+    #[test]
+    fn look_at_basis() {
+        let eye = Tuple::point_from_underlying(0.0, 0.0, 0.0);
+        let (right, up, forward) = Tuple::look_at_basis(
+            eye,
+            Tuple::point_from_underlying(0.0, 0.0, -5.0),
+            Tuple::vector_from_underlying(0.0, 2.0, 0.0),
+        );
+        assert_eq!(right, Tuple::vector_from_underlying(1.0, 0.0, 0.0));
+        assert_eq!(up, Tuple::vector_from_underlying(0.0, 1.0, 0.0));
+        assert_eq!(forward, Tuple::vector_from_underlying(0.0, 0.0, -1.0));
+
+        let (right, up, forward) = Tuple::look_at_basis(
+            eye,
+            Tuple::point_from_underlying(0.0, -3.0, 0.0),
+            Tuple::vector_from_underlying(0.0, 1.0, 0.0),
+        );
+        for v in [right, up, forward] {
+            assert!(v.is_vector() && v.is_unit());
+        }
+        assert_eq!(right * up, 0.0);
+        assert_eq!(right * forward, 0.0);
+        assert_eq!(up * forward, 0.0);
+        assert_eq!(forward, Tuple::vector_from_underlying(0.0, -1.0, 0.0));
+    }
+
+    #[test]
+    fn fibonacci_sphere() {
+        let directions = Tuple::fibonacci_sphere(1000);
+        assert_eq!(directions.len(), 1000);
+        assert!(directions.iter().all(|d| d.is_vector() && d.is_unit()));
+        let centroid = directions
+            .iter()
+            .fold(Tuple::vector_from_underlying(0.0, 0.0, 0.0), |acc, d| {
+                acc + *d
+            })
+            / directions.len() as UnderlyingFloat;
+        assert!(centroid.magnitude().0 < 1e-2);
+        assert!(Tuple::fibonacci_sphere(0).is_empty());
+    }
+
+    #[test]
+    fn closest_point_on_segment() {
+        let a = Tuple::point_from_underlying(-1.0, 0.0, 0.0);
+        let b = Tuple::point_from_underlying(1.0, 0.0, 0.0);
+        let closest =
+            |x, y| Tuple::closest_point_on_segment(Tuple::point_from_underlying(x, y, 0.0), a, b);
+        assert_eq!(
+            closest(0.5, 2.0),
+            Tuple::point_from_underlying(0.5, 0.0, 0.0)
+        );
+        assert_eq!(closest(-3.0, 1.0), a);
+        assert_eq!(closest(3.0, -1.0), b);
+        assert_eq!(Tuple::closest_point_on_segment(b, a, a), a);
+
+        let short_a = Tuple::point_from_underlying(0.0, 0.0, 0.0);
+        let short_b = Tuple::point_from_underlying(1e-3, 0.0, 0.0);
+        let closest = Tuple::closest_point_on_segment(
+            Tuple::point_from_underlying(1.0, 1.0, 0.0),
+            short_a,
+            short_b,
+        );
+        assert_eq!(closest.get_x().0, 1e-3);
+    }
+
+    #[test]
+    fn plane_from_points() {
+        let (normal, distance) = Tuple::plane_from_points(
+            Tuple::point_from_underlying(0.0, 0.0, 2.0),
+            Tuple::point_from_underlying(0.0, 1.0, 2.0),
+            Tuple::point_from_underlying(1.0, 0.0, 2.0),
+        )
+        .unwrap();
+        assert_eq!(normal, Tuple::vector_from_underlying(0.0, 0.0, -1.0));
+        assert_eq!(distance, -2.0);
+
+        assert_eq!(
+            Tuple::plane_from_points(
+                Tuple::point_from_underlying(0.0, 0.0, 0.0),
+                Tuple::point_from_underlying(1.0, 1.0, 1.0),
+                Tuple::point_from_underlying(2.0, 2.0, 2.0),
+            ),
+            None
+        );
+
+        let (normal, distance) = Tuple::plane_from_points(
+            Tuple::point_from_underlying(0.0, 0.0, 2.0),
+            Tuple::point_from_underlying(0.0, 1e-3, 2.0),
+            Tuple::point_from_underlying(1e-3, 0.0, 2.0),
+        )
+        .unwrap();
+        assert_eq!(normal, Tuple::vector_from_underlying(0.0, 0.0, -1.0));
+        assert_eq!(distance, -2.0);
+    }
+
+    #[test]
+    fn reflect() {
+        let v = Tuple::vector_from_underlying(1.0, -1.0, 0.0);
+        let n = Tuple::vector_from_underlying(0.0, 1.0, 0.0);
+        assert_eq!(v.reflect(n), Tuple::vector_from_underlying(1.0, 1.0, 0.0));
+
+        let sqrt_half = 0.5f64.sqrt();
+        let v = Tuple::vector_from_underlying(0.0, -1.0, 0.0);
+        let n = Tuple::vector_from_underlying(sqrt_half, sqrt_half, 0.0);
+        assert_eq!(v.reflect(n), Tuple::vector_from_underlying(1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "reflect requires a vector normal")]
+    fn reflect_panics_on_point_normal() {
+        Tuple::vector_from_underlying(1.0, -1.0, 0.0)
+            .reflect(Tuple::point_from_underlying(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn reflect_jittered() {
+        let v = Tuple::vector_from_underlying(1.0, -1.0, 0.0);
+        let n = Tuple::vector_from_underlying(0.0, 1.0, 0.0);
+        let sample = (Float(0.7), Float(0.3));
+        assert_eq!(v.reflect_jittered(n, Float(0.0), sample), v.reflect(n));
+
+        let reflected = v.reflect(n).normalize();
+        let roughness = Float(0.2);
+        let cos_max = (roughness * std::f64::consts::FRAC_PI_2).cos();
+        for (s, t) in [(0.0, 0.0), (1.0, 0.25), (0.5, 0.5), (1.0, 1.0)] {
+            let jittered = v.reflect_jittered(n, roughness, (Float(s), Float(t)));
+            assert_eq!(jittered.magnitude(), v.magnitude());
+            assert!(jittered.is_vector());
+            assert!(jittered.normalize().dot(reflected) >= cos_max);
+        }
+        let edge = v.reflect_jittered(n, roughness, (Float(1.0), Float(0.0)));
+        assert_eq!(edge.normalize().dot(reflected), cos_max);
+    }
+
+    #[test]
+    fn reflect_all() {
+        let sqrt_half = 0.5f64.sqrt();
+        let normal = Tuple::vector_from_underlying(sqrt_half, sqrt_half, 0.0);
+        let original = [
+            Tuple::vector_from_underlying(1.0, -1.0, 0.0),
+            Tuple::vector_from_underlying(0.0, -1.0, 0.0),
+            Tuple::vector_from_underlying(2.0, 3.0, -4.0),
+        ];
+        let mut vectors = original;
+        Tuple::reflect_all(&mut vectors, normal);
+        assert_eq!(vectors, original.map(|v| v.reflect(normal)));
+        assert_eq!(
+            vectors,
+            [
+                Tuple::vector_from_underlying(1.0, -1.0, 0.0),
+                Tuple::vector_from_underlying(1.0, 0.0, 0.0),
+                Tuple::vector_from_underlying(-3.0, -2.0, -4.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn try_normalize() {
+        assert_eq!(Tuple::zero().try_normalize(), None);
+        assert_eq!(
+            Tuple::vector_from_underlying(1e-7, 0.0, 0.0).try_normalize(),
+            None
+        );
+        let normalized = Tuple::vector_from_underlying(1.0, 2.0, 3.0)
+            .try_normalize()
+            .unwrap();
+        assert_eq!(normalized.magnitude(), 1.0);
+        assert_eq!(
+            normalized,
+            Tuple::vector_from_underlying(1.0, 2.0, 3.0).normalize()
+        );
+    }
+
+    #[test]
+    fn distance() {
+        let a = Tuple::point_from_underlying(1.0, 0.0, 0.0);
+        let b = Tuple::point_from_underlying(4.0, 0.0, 0.0);
+        assert_eq!(a.distance(b), 3.0);
+        assert_eq!(a.distance_squared(b), 9.0);
+        assert_eq!(b.distance(a), 3.0);
+        let c = Tuple::point_from_underlying(1.0, 2.0, 2.0);
+        assert_eq!(a.distance(c), 8.0f64.sqrt());
+        assert_eq!(a.distance(a), 0.0);
+    }
+
+    #[test]
+    fn array_round_trip() {
+        let arr = [1.5, -2.0, 0.25, 1.0];
+        let back: [UnderlyingFloat; 4] = Tuple::from(arr).into();
+        assert_eq!(back.map(Float), arr.map(Float));
+        let floats = arr.map(Float);
+        assert_eq!(Tuple::from(floats), Tuple::from(arr));
+        assert_eq!(Tuple::from(arr).elements, floats);
+        assert_eq!(
+            Tuple::point_from_underlying(1.0, 2.0, 3.0).to_underlying_array(),
+            [1.0, 2.0, 3.0, 1.0]
+        );
+    }
+
+    #[test]
+    fn project_and_reject() {
+        let v = Tuple::vector_from_underlying(2.0, 2.0, 0.0);
+        let x = Tuple::vector_from_underlying(1.0, 0.0, 0.0);
+        assert_eq!(
+            v.project_onto(x),
+            Tuple::vector_from_underlying(2.0, 0.0, 0.0)
+        );
+        assert_eq!(
+            v.project_onto(x * 5.0),
+            Tuple::vector_from_underlying(2.0, 0.0, 0.0)
+        );
+
+        let a = Tuple::vector_from_underlying(1.0, -3.0, 2.5);
+        let b = Tuple::vector_from_underlying(-2.0, 0.5, 4.0);
+        let projection = a.project_onto(b);
+        let rejection = a.reject_from(b);
+        assert_eq!(projection + rejection, a);
+        assert_eq!(rejection * b, 0.0);
+        assert_eq!(projection % b, Tuple::zero());
+    }
+
+    #[test]
+    fn angle_between() {
+        use std::f64::consts::{FRAC_PI_2, PI};
+        let x = Tuple::vector_from_underlying(2.0, 0.0, 0.0);
+        let y = Tuple::vector_from_underlying(0.0, 0.5, 0.0);
+        assert_eq!(x.angle_between(y), FRAC_PI_2);
+        assert_eq!(x.angle_between(x * 3.0), 0.0);
+        assert_eq!(x.angle_between(-x), PI);
+        assert_eq!(x.angle_between(Tuple::zero()), 0.0);
+        let v = Tuple::vector_from_underlying(0.1, 0.2, 0.3);
+        assert!(!v.angle_between(v * 7.0).0.is_nan());
+        let short_x = Tuple::vector_from_underlying(1e-3, 0.0, 0.0);
+        let short_y = Tuple::vector_from_underlying(0.0, 1e-3, 0.0);
+        assert_eq!(short_x.angle_between(short_y), FRAC_PI_2);
+    }
+
+    #[test]
+    fn into_iter() {
+        let a = Tuple::new_from_underlying(1.0, -2.0, 3.5, 4.0);
+        assert_eq!(a.into_iter().sum::<Float>(), a[0] + a[1] + a[2] + a[3]);
+        let mut count = 0;
+        for (i, f) in (&a).into_iter().enumerate() {
+            assert_eq!(*f, a[i]);
+            count += 1;
+        }
+        assert_eq!(count, 4);
+        let collected: Vec<Float> = a.into_iter().collect();
+        assert_eq!(collected, a.elements);
+    }
+
+    #[test]
+    fn index() {
+        let mut a = Tuple::new_from_underlying(1.0, 2.0, 3.0, 4.0);
+        for i in 0..4 {
+            assert_eq!(a[i], (i + 1) as UnderlyingFloat);
+            a[i] = Float(-(i as UnderlyingFloat));
+        }
+        assert_eq!(a, Tuple::new_from_underlying(0.0, -1.0, -2.0, -3.0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_out_of_range() {
+        let a = Tuple::zero();
+        let _ = a[4];
+    }
+
     #[test]
     fn mutation() {
         let mut vec = Tuple::vector_from_underlying(1.0, 2.0, 3.0);